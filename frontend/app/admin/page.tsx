@@ -108,7 +108,12 @@ export default function AdminPage() {
           question,
           description,
           new BN(endTime),
-          category
+          category,
+          null, // Protocol default fee
+          { manual: {} },
+          "",
+          { binary: {} },
+          null // Not conditional
         )
         .accounts({
           market: marketPda,
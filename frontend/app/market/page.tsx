@@ -10,7 +10,7 @@ import {
   ArrowLeft, Flame, Target, Loader2
 } from "lucide-react";
 import Link from "next/link";
-import { usePredictionMarkets, Market } from "@/lib/solana/hooks/usePredictionMarkets";
+import { usePredictionMarkets, Market, YES_INDEX, NO_INDEX } from "@/lib/solana/hooks/usePredictionMarkets";
 import { useWallet } from "@solana/wallet-adapter-react";
 import { BetSuccessModal } from "@/components/BetSuccessModal";
 
@@ -85,7 +85,7 @@ function MarketContent() {
       const multiplier = selectedSide ? market.yesMultiplier : market.noMultiplier;
       const potentialPayout = parseFloat(calculatePayout());
 
-      await placeBet(market.publicKey, amount, selectedSide);
+      await placeBet(market.publicKey, amount, selectedSide ? YES_INDEX : NO_INDEX);
 
       setSuccessBetData({
         question: market.question,
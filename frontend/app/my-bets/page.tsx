@@ -8,7 +8,7 @@ import {
 } from "lucide-react";
 import Link from "next/link";
 import { useWallet } from "@solana/wallet-adapter-react";
-import { usePredictionMarkets, Bet, Market } from "@/lib/solana/hooks/usePredictionMarkets";
+import { usePredictionMarkets, Bet, Market, YES_INDEX } from "@/lib/solana/hooks/usePredictionMarkets";
 import { useUsdcBalance } from "@/hooks/useUsdcBalance";
 import { RetroGrid } from "@/components/RetroGrid";
 import { WalletButton } from "@/components/WalletButton";
@@ -55,7 +55,7 @@ export default function MyBetsPage() {
 
       // Calculate potential payout based on current pool sizes
       const totalPool = market.yesPool + market.noPool;
-      const sidePool = bet.prediction ? market.yesPool : market.noPool;
+      const sidePool = bet.outcomeIndex === YES_INDEX ? market.yesPool : market.noPool;
       const potentialPayout = sidePool > 0 ? (bet.amount / sidePool) * totalPool : bet.amount * 2;
 
       const betWithMarket: BetWithMarket = {
@@ -66,7 +66,9 @@ export default function MyBetsPage() {
       };
 
       if (market.status === "Resolved") {
-        const userWon = market.outcome === bet.prediction;
+        // Bets placed after an early-resolved event get their stake back
+        const refundable = market.eventOccurredAt !== null && bet.timestamp > market.eventOccurredAt;
+        const userWon = refundable || market.outcome === (bet.outcomeIndex === YES_INDEX);
 
         if (userWon && !bet.claimed) {
           betWithMarket.status = "claimable";
@@ -367,11 +369,11 @@ export default function MyBetsPage() {
                               <div className="flex-1 min-w-0">
                                 <div className="flex items-center gap-2 mb-1">
                                   <span className={`px-2 py-0.5 rounded-full text-[10px] font-bold ${
-                                    bet.prediction
+                                    bet.outcomeIndex === YES_INDEX
                                       ? "bg-[#00ff88]/20 text-[#00ff88]"
                                       : "bg-[#ff0044]/20 text-[#ff0044]"
                                   }`}>
-                                    {bet.prediction ? "YES" : "NO"}
+                                    {bet.outcomeIndex === YES_INDEX ? "YES" : "NO"}
                                   </span>
                                   <span className="text-xs text-gray-500 flex items-center gap-1">
                                     <Clock className="w-3 h-3" />
@@ -426,7 +428,7 @@ export default function MyBetsPage() {
                                   {bet.marketData?.question || "Unknown Market"}
                                 </h3>
                                 <div className="text-xs text-gray-400 mt-1">
-                                  Bet {bet.prediction ? "YES" : "NO"} • Won!
+                                  Bet {bet.outcomeIndex === YES_INDEX ? "YES" : "NO"} • Won!
                                 </div>
                               </div>
                               <div className="flex flex-col items-end gap-2">
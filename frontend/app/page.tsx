@@ -9,7 +9,7 @@ import { SwipeableMarketStack } from "@/components/SwipeableMarketStack";
 import { GameOverlay } from "@/components/GameOverlay";
 import { BetSuccessModal } from "@/components/BetSuccessModal";
 import { ArcadeModal } from "@/components/ArcadeModal";
-import { usePredictionMarkets, Market } from "@/lib/solana/hooks/usePredictionMarkets";
+import { usePredictionMarkets, Market, YES_INDEX, NO_INDEX } from "@/lib/solana/hooks/usePredictionMarkets";
 import { useUsdcBalance, useSolBalance } from "@/hooks/useUsdcBalance";
 import { useSoundEffects } from "@/hooks/useSoundEffects";
 import { WalletButton } from "@/components/WalletButton";
@@ -81,7 +81,7 @@ export default function ArenaPage() {
     userBets.forEach(bet => {
       totalWagered += bet.amount;
       const marketOutcome = marketOutcomes.get(bet.market);
-      if (marketOutcome !== undefined && bet.prediction === marketOutcome) {
+      if (marketOutcome !== undefined && (bet.outcomeIndex === YES_INDEX) === marketOutcome) {
         totalWins++;
       }
     });
@@ -89,7 +89,7 @@ export default function ArenaPage() {
     // Calculate current streak (simplified - just count claimed wins)
     const wonBets = userBets.filter(bet => {
       const outcome = marketOutcomes.get(bet.market);
-      return outcome !== undefined && bet.prediction === outcome;
+      return outcome !== undefined && (bet.outcomeIndex === YES_INDEX) === outcome;
     });
     const currentStreak = wonBets.length > 0 ? Math.min(wonBets.length, 10) : 0;
 
@@ -165,7 +165,7 @@ export default function ArenaPage() {
 
       try {
         // Place the actual bet on blockchain
-        await placeBet(marketId, amount, prediction);
+        await placeBet(marketId, amount, prediction ? YES_INDEX : NO_INDEX);
 
         playBet();
 
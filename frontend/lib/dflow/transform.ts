@@ -125,6 +125,7 @@ export function transformDFlowMarket(
     resolutionBond: 0,
     challengeDeadline: null,
     isFinalized: isResolved,
+    eventOccurredAt: null,
     // Extended fields for DFlow integration
     dflowTicker: market.ticker,
     dflowEventTicker: market.event_ticker,
@@ -41,6 +41,21 @@ function toNum(value: any): number {
   return 0;
 }
 
+// Role::Treasurer in the program's role grants
+const ROLE_TREASURER = 2;
+
+// Binary bets are placed on outcome index 0 (YES) or 1 (NO)
+export const YES_INDEX = 0;
+export const NO_INDEX = 1;
+
+// Map an on-chain Outcome enum to a YES/NO result, null while unresolved or not binary
+function outcomeToBool(outcome: any): boolean | null {
+  if (!outcome) return null;
+  if ('yes' in outcome) return true;
+  if ('no' in outcome) return false;
+  return null;
+}
+
 export interface Market {
   publicKey: string;
   id: number;
@@ -68,6 +83,7 @@ export interface Market {
   resolutionBond: number;
   challengeDeadline: number | null;
   isFinalized: boolean;
+  eventOccurredAt: number | null; // Set on early resolution; bets after it are refunded
 }
 
 export interface Bet {
@@ -75,7 +91,7 @@ export interface Bet {
   user: string;
   market: string;
   amount: number;
-  prediction: boolean;
+  outcomeIndex: number; // 0 = YES, 1 = NO for binary markets
   tokensReceived: number;
   timestamp: number;
   claimed: boolean;
@@ -351,7 +367,7 @@ export function usePredictionMarkets() {
           noPool,
           totalVolume,
           isResolved: status === "Resolved",
-          outcome: data.outcome !== null && data.outcome !== undefined ? data.outcome : null,
+          outcome: outcomeToBool(data.outcome),
           endTime,
           createdAt: toNum(data.createdAt),
           yesPrice,
@@ -367,6 +383,7 @@ export function usePredictionMarkets() {
           resolutionBond: toNum(data.resolutionBond) / Math.pow(10, USDC_DECIMALS),
           challengeDeadline: data.challengeDeadline ? toNum(data.challengeDeadline) : null,
           isFinalized: data.isFinalized || false,
+          eventOccurredAt: data.eventOccurredAt ? toNum(data.eventOccurredAt) : null,
         };
       });
 
@@ -402,7 +419,7 @@ export function usePredictionMarkets() {
           user: data.user.toString(),
           market: data.market.toString(),
           amount: toNum(data.amount) / Math.pow(10, USDC_DECIMALS),
-          prediction: data.prediction,
+          outcomeIndex: data.outcomeIndex,
           tokensReceived: toNum(data.tokens_received) / Math.pow(10, USDC_DECIMALS),
           timestamp: toNum(data.timestamp),
           claimed: data.claimed,
@@ -435,7 +452,7 @@ export function usePredictionMarkets() {
   };
 
   // Place a bet - Mobile Wallet Adapter Compatible
-  const placeBet = async (marketAddress: string, amount: number, prediction: boolean): Promise<string> => {
+  const placeBet = async (marketAddress: string, amount: number, outcomeIndex: number): Promise<string> => {
     if (!program || !wallet || !walletAdapter.publicKey) {
       throw new Error("Wallet not connected");
     }
@@ -525,7 +542,7 @@ export function usePredictionMarkets() {
         // Build the transaction using Anchor's transaction() method
         // IMPORTANT: Use walletAdapter.publicKey for consistency
         const betTx = await program.methods
-          .placeBet(new BN(marketId), amountInSmallestUnits, prediction)
+          .placeBet(new BN(marketId), amountInSmallestUnits, outcomeIndex)
           .accounts({
             market: marketPubkey,
             userStats: userStatsPda,
@@ -543,7 +560,7 @@ export function usePredictionMarkets() {
         console.log("[PlaceBet] Using standard Anchor RPC flow");
 
         signature = await program.methods
-          .placeBet(new BN(marketId), amountInSmallestUnits, prediction)
+          .placeBet(new BN(marketId), amountInSmallestUnits, outcomeIndex)
           .accounts({
             market: marketPubkey,
             userStats: userStatsPda,
@@ -618,10 +635,19 @@ export function usePredictionMarkets() {
         throw new Error("Market is not resolved yet. Wait for resolution.");
       }
 
-      // Verify user won
+      // Verify user won (invalid markets and bets placed after an early-resolved event are refunded)
       const outcome = (marketAccount as any).outcome;
-      const userPrediction = (betAccount as any).prediction;
-      if (outcome !== userPrediction) {
+      const betIndex = (betAccount as any).outcomeIndex;
+      const eventOccurredAt = (marketAccount as any).eventOccurredAt;
+      const lateBet = eventOccurredAt != null && toNum((betAccount as any).timestamp) > toNum(eventOccurredAt);
+      const won =
+        'invalid' in outcome ||
+        lateBet ||
+        ('yes' in outcome && betIndex === YES_INDEX) ||
+        ('no' in outcome && betIndex === NO_INDEX) ||
+        ('category' in outcome && betIndex === outcome.category[0]) ||
+        'scalar' in outcome;
+      if (!won) {
         throw new Error("You did not win this bet. Cannot claim.");
       }
 
@@ -718,10 +744,15 @@ export function usePredictionMarkets() {
       }
 
       const tx = await program.methods
-        .resolveMarket(new BN(marketId), outcome)
+        .resolveMarket(
+          new BN(marketId),
+          outcome ? { yes: {} } : { no: {} },
+          { uri: "", hash: new Array(32).fill(0) },
+          null
+        )
         .accounts({
           market: marketPubkey,
-          creator: wallet.publicKey,
+          resolver: wallet.publicKey,
         })
         .rpc();
 
@@ -737,7 +768,7 @@ export function usePredictionMarkets() {
     }
   };
 
-  // Withdraw fees from a resolved market (ADMIN ONLY) - Mobile Wallet Adapter Compatible
+  // Withdraw fees from a resolved market (TREASURER ROLE OR ADMIN) - Mobile Wallet Adapter Compatible
   const withdrawFees = async (marketAddress: string, marketId: number): Promise<string> => {
     if (!program || !wallet || !walletAdapter.publicKey) {
       throw new Error("Wallet not connected");
@@ -748,29 +779,44 @@ export function usePredictionMarkets() {
     try {
       const marketPubkey = new PublicKey(marketAddress);
 
-      // Get admin's USDC token account
-      const adminTokenAccount = await getAssociatedTokenAddress(
+      // Fees always go to the protocol fee recipient set in the config
+      const [configPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("config")],
+        program.programId
+      );
+      const configAccount = await (program as any).account.config.fetch(configPda);
+      const feeRecipient = (configAccount as any).feeRecipient as PublicKey;
+
+      // Get the fee recipient's USDC token account
+      const feeRecipientTokenAccount = await getAssociatedTokenAddress(
         USDC_MINT,
-        wallet.publicKey
+        feeRecipient
       );
 
       // Check if account exists, create if not using mobile-compatible method
-      const accountInfo = await connection.getAccountInfo(adminTokenAccount);
+      const accountInfo = await connection.getAccountInfo(feeRecipientTokenAccount);
       if (!accountInfo) {
-        console.log("[WithdrawFees] Creating admin USDC token account...");
+        console.log("[WithdrawFees] Creating fee recipient USDC token account...");
         const ataTransaction = new Transaction();
         ataTransaction.add(
           createAssociatedTokenAccountInstruction(
             walletAdapter.publicKey,
-            adminTokenAccount,
-            walletAdapter.publicKey,
+            feeRecipientTokenAccount,
+            feeRecipient,
             USDC_MINT
           )
         );
         await sendMobileCompatibleTransaction(ataTransaction, `ata-${txId}`);
-        console.log("[WithdrawFees] Admin USDC token account created");
+        console.log("[WithdrawFees] Fee recipient USDC token account created");
       }
 
+      // Treasurers sign with their role grant, the admin needs none
+      const [treasurerRolePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), Buffer.from([ROLE_TREASURER]), wallet.publicKey.toBuffer()],
+        program.programId
+      );
+      const treasurerRole = (await connection.getAccountInfo(treasurerRolePda)) ? treasurerRolePda : null;
+
       // Derive vault PDA
       const [vaultPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("vault"), new BN(marketId).toArrayLike(Buffer, "le", 8)],
@@ -790,8 +836,9 @@ export function usePredictionMarkets() {
           .accounts({
             market: marketPubkey,
             vault: vaultPda,
-            adminTokenAccount: adminTokenAccount,
-            admin: walletAdapter.publicKey,
+            feeRecipientTokenAccount: feeRecipientTokenAccount,
+            treasurer: walletAdapter.publicKey,
+            treasurerRole: treasurerRole,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .transaction();
@@ -806,8 +853,9 @@ export function usePredictionMarkets() {
           .accounts({
             market: marketPubkey,
             vault: vaultPda,
-            adminTokenAccount: adminTokenAccount,
-            admin: wallet.publicKey,
+            feeRecipientTokenAccount: feeRecipientTokenAccount,
+            treasurer: wallet.publicKey,
+            treasurerRole: treasurerRole,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .rpc();
@@ -883,7 +931,7 @@ export function usePredictionMarkets() {
         noPool,
         totalVolume,
         isResolved: status === "Resolved",
-        outcome: (data as any).outcome !== null && (data as any).outcome !== undefined ? (data as any).outcome : null,
+        outcome: outcomeToBool((data as any).outcome),
         endTime,
         createdAt: toNum((data as any).createdAt),
         yesPrice,
@@ -899,6 +947,7 @@ export function usePredictionMarkets() {
         resolutionBond: toNum((data as any).resolutionBond) / Math.pow(10, USDC_DECIMALS),
         challengeDeadline: (data as any).challengeDeadline ? toNum((data as any).challengeDeadline) : null,
         isFinalized: (data as any).isFinalized || false,
+        eventOccurredAt: (data as any).eventOccurredAt ? toNum((data as any).eventOccurredAt) : null,
       };
     } catch (error: any) {
       console.error("Error fetching market:", error);
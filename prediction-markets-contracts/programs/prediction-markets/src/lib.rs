@@ -1,16 +1,209 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use solana_sha256_hasher::hashv;
+
+pub mod oracle;
+
+use oracle::PriceCondition;
 
 declare_id!("G9tuE1qzcurDeUQcfgkpeEkLgJC3yGsF7crn53pzD79j");
 
-// Admin public key - only this wallet can create markets and withdraw fees
-pub const ADMIN_PUBKEY: &str = "5TY5gts9AktYJMN6S8dGDzjAxmZLbxgbWrhRPpLfxYUD";
+// Fees are expressed in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Multisig limits
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+pub const MAX_ACTION_ACCOUNTS: usize = 16;
+pub const MAX_ACTION_DATA_LEN: usize = 1024;
+
+// Largest resolver committee a market can name
+pub const MAX_COMMITTEE_SIZE: usize = 10;
+
+// Categorical market limits
+pub const MAX_OUTCOMES: usize = 8;
+pub const MAX_OUTCOME_LABEL_LEN: usize = 32;
+
+// Most markets a single parlay can combine
+pub const MAX_PARLAY_LEGS: usize = 5;
+
+// Virtual liquidity on each side of a binary market (1000 USDC, 6 decimals)
+pub const VIRTUAL_POOL: u64 = 1_000_000_000;
 
 #[program]
 pub mod prediction_markets {
     use super::*;
 
-    /// Create a new prediction market (ADMIN ONLY)
+    /// Initialize the protocol config (program upgrade authority only)
+    /// The signer becomes the protocol admin
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        params: ConfigParams,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = ctx.accounts.authority.key();
+        config.pending_admin = None;
+        config.paused = false;
+        config.betting_paused = false;
+        config.claiming_paused = false;
+        config.creation_paused = false;
+        config.withdrawals_paused = false;
+        config.collateral_mint = ctx.accounts.collateral_mint.key();
+        config.bump = ctx.bumps.config;
+        config.apply(params)?;
+
+        msg!("Protocol config initialized (admin: {})", config.admin);
+        Ok(())
+    }
+
+    /// Update protocol parameters (ADMIN ONLY)
+    /// The collateral mint is fixed at initialization: market vaults, the bond
+    /// escrow and the parlay vault are all created for it
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        params: ConfigParams,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(ctx.accounts.admin.key() == config.admin, MarketError::Unauthorized);
+
+        config.apply(params)?;
+
+        msg!("Protocol config updated");
+        Ok(())
+    }
+
+    /// Propose a new admin (ADMIN ONLY)
+    /// The proposed wallet must call accept_admin before it gains any rights
+    pub fn propose_admin(
+        ctx: Context<ManageAdmin>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(ctx.accounts.admin.key() == config.admin, MarketError::Unauthorized);
+
+        config.pending_admin = Some(new_admin);
+
+        msg!("Admin transfer proposed: {} -> {}", config.admin, new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer (PENDING ADMIN ONLY)
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        let pending_admin = config.pending_admin.ok_or(MarketError::NoPendingAdmin)?;
+        require!(ctx.accounts.new_admin.key() == pending_admin, MarketError::NotPendingAdmin);
+
+        config.admin = pending_admin;
+        config.pending_admin = None;
+
+        msg!("Admin transfer accepted: {} is now admin", config.admin);
+        Ok(())
+    }
+
+    /// Cancel a pending admin transfer (ADMIN ONLY)
+    pub fn cancel_admin_transfer(
+        ctx: Context<ManageAdmin>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(ctx.accounts.admin.key() == config.admin, MarketError::Unauthorized);
+        require!(config.pending_admin.is_some(), MarketError::NoPendingAdmin);
+
+        config.pending_admin = None;
+
+        msg!("Admin transfer cancelled");
+        Ok(())
+    }
+
+    /// Grant a role to a wallet (ADMIN ONLY)
+    pub fn grant_role(
+        ctx: Context<GrantRole>,
+        role: Role,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, MarketError::Unauthorized);
+
+        let grant = &mut ctx.accounts.role_grant;
+        let clock = Clock::get()?;
+
+        grant.role = role;
+        grant.wallet = wallet;
+        grant.granted_by = ctx.accounts.admin.key();
+        grant.granted_at = clock.unix_timestamp;
+        grant.bump = ctx.bumps.role_grant;
+
+        msg!("Role {:?} granted to {}", role, wallet);
+        Ok(())
+    }
+
+    /// Revoke a role from a wallet (ADMIN ONLY)
+    pub fn revoke_role(
+        ctx: Context<RevokeRole>,
+        role: Role,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, MarketError::Unauthorized);
+
+        msg!("Role {:?} revoked from {}", role, wallet);
+        Ok(())
+    }
+
+    /// Pause the whole protocol or a single operation (GUARDIAN ROLE OR ADMIN)
+    pub fn pause_protocol(
+        ctx: Context<SetPause>,
+        scope: PauseScope,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            ctx.accounts.config.has_role(&authority, &ctx.accounts.guardian_role),
+            MarketError::Unauthorized
+        );
+
+        ctx.accounts.config.set_paused(scope, true);
+
+        emit!(ProtocolPaused {
+            scope,
+            authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Protocol paused: {:?}", scope);
+        Ok(())
+    }
+
+    /// Lift a pause set by pause_protocol (GUARDIAN ROLE OR ADMIN)
+    pub fn unpause_protocol(
+        ctx: Context<SetPause>,
+        scope: PauseScope,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            ctx.accounts.config.has_role(&authority, &ctx.accounts.guardian_role),
+            MarketError::Unauthorized
+        );
+
+        ctx.accounts.config.set_paused(scope, false);
+
+        emit!(ProtocolUnpaused {
+            scope,
+            authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Protocol unpaused: {:?}", scope);
+        Ok(())
+    }
+
+    /// Create a new prediction market
+    /// Wallets without the market creator role post a refundable creation bond
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         ctx: Context<CreateMarket>,
         market_id: u64,
@@ -18,85 +211,238 @@ pub mod prediction_markets {
         description: String,
         end_time: i64,
         category: String,
+        fee_bps: Option<u16>, // None = protocol default
+        resolution_mode: ResolutionMode,
+        resolution_source: String, // Where the outcome will be read from
+        market_type: MarketType,
+        required_outcome: Option<Outcome>, // Some = conditional on `parent_market` resolving to it
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::Creation)?;
+
+        let clock = Clock::get()?;
+        let fee_bps = ctx.accounts.config.market_fee_bps(fee_bps)?;
+
+        let parent_market = match required_outcome {
+            Some(required) => {
+                let parent = ctx.accounts.parent_market
+                    .as_ref()
+                    .ok_or(MarketError::MissingParentMarket)?;
+                parent.require_valid_outcome(required)?;
+                Some(parent.key())
+            }
+            None => None,
+        };
+
+        let details = MarketDetails {
+            question,
+            description,
+            end_time,
+            category,
+            resolution_mode,
+            resolution_source,
+            market_type,
+            parent_market,
+            required_outcome,
+        };
+        details.validate(clock.unix_timestamp)?;
+
+        // Curators create for free, everyone else escrows the configured bond
+        let is_curator = ctx.accounts.config.has_role(&ctx.accounts.creator.key(), &ctx.accounts.creator_role);
+        let creation_bond = if is_curator { 0 } else { ctx.accounts.config.creation_bond };
+
+        if creation_bond > 0 {
+            let creator_token_account = ctx.accounts.creator_token_account
+                .as_ref()
+                .ok_or(MarketError::MissingBondTokenAccount)?;
+
+            let cpi_accounts = Transfer {
+                from: creator_token_account.to_account_info(),
+                to: ctx.accounts.bond_escrow.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, creation_bond)?;
+        }
+
+        let market = &mut ctx.accounts.market;
+
+        market.initialize(
+            market_id,
+            ctx.accounts.creator.key(),
+            details,
+            fee_bps,
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
+        market.vault = ctx.accounts.vault.key();
+        market.vault_bump = ctx.bumps.vault;
+        market.creation_bond = creation_bond;
+
+        msg!("Market created: {} (ID: {})", market.question, market_id);
+        Ok(())
+    }
+
+    /// Suggest a market for curation (PERMISSIONLESS)
+    /// The proposal only becomes tradeable once a curator approves it
+    pub fn propose_market(
+        ctx: Context<ProposeMarket>,
+        proposal_id: u64,
+        question: String,
+        description: String,
+        end_time: i64,
+        category: String,
+        resolution_source: String,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::Creation)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        let details = MarketDetails {
+            question,
+            description,
+            end_time,
+            category,
+            resolution_mode: ResolutionMode::Manual,
+            resolution_source,
+            market_type: MarketType::Binary,
+            parent_market: None,
+            required_outcome: None,
+        };
+        details.validate(clock.unix_timestamp)?;
+
+        proposal.id = proposal_id;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.question = details.question;
+        proposal.description = details.description;
+        proposal.category = details.category;
+        proposal.resolution_source = details.resolution_source;
+        proposal.end_time = details.end_time;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.status = ProposalStatus::Pending;
+        proposal.market = None;
+        proposal.reviewed_by = None;
+        proposal.bump = ctx.bumps.proposal;
+
+        msg!("Market proposed: {} (proposal ID: {})", proposal.question, proposal_id);
+        Ok(())
+    }
+
+    /// Approve a proposal, creating its market and vault (MARKET CREATOR ROLE OR ADMIN)
+    /// The proposer becomes the market creator
+    pub fn approve_proposal(
+        ctx: Context<ApproveProposal>,
+        _proposal_id: u64,
+        market_id: u64,
     ) -> Result<()> {
-        // Only admin can create markets
+        ctx.accounts.config.require_not_paused(PauseScope::Creation)?;
         require!(
-            ctx.accounts.creator.key().to_string() == ADMIN_PUBKEY,
+            ctx.accounts.config.has_role(&ctx.accounts.curator.key(), &ctx.accounts.curator_role),
             MarketError::Unauthorized
         );
 
-        let market = &mut ctx.accounts.market;
+        let proposal = &mut ctx.accounts.proposal;
         let clock = Clock::get()?;
 
-        require!(question.len() <= 200, MarketError::QuestionTooLong);
-        require!(description.len() <= 500, MarketError::DescriptionTooLong);
-        require!(end_time > clock.unix_timestamp, MarketError::EndTimeInPast);
-        require!(end_time < clock.unix_timestamp + 30 * 24 * 60 * 60, MarketError::EndTimeTooFar);
-
-        market.id = market_id;
-        market.question = question;
-        market.description = description;
-        market.creator = ctx.accounts.creator.key();
-        market.created_at = clock.unix_timestamp;
-        market.end_time = end_time;
-        market.resolution_time = None;
-        market.outcome = None;
-        market.category = category;
+        require!(proposal.status == ProposalStatus::Pending, MarketError::ProposalNotPending);
 
-        // Initialize AMM pools with 50/50 ratio (1000 USDC each side for virtual liquidity)
-        market.yes_pool = 1_000_000_000; // 1000 USDC (6 decimals)
-        market.no_pool = 1_000_000_000;  // 1000 USDC
-        market.total_yes_bets = 0;
-        market.total_no_bets = 0;
-        market.total_volume = 0;
-        market.total_bets_count = 0;
-        market.unique_bettors = 0;
-
-        market.fee_percentage = 2; // 2% platform fee
-        market.status = MarketStatus::Active;
+        let details = MarketDetails {
+            question: proposal.question.clone(),
+            description: proposal.description.clone(),
+            end_time: proposal.end_time,
+            category: proposal.category.clone(),
+            resolution_mode: ResolutionMode::Manual,
+            resolution_source: proposal.resolution_source.clone(),
+            market_type: MarketType::Binary,
+            parent_market: None,
+            required_outcome: None,
+        };
+        details.validate(clock.unix_timestamp)?;
+
+        let market = &mut ctx.accounts.market;
+
+        market.initialize(
+            market_id,
+            proposal.proposer,
+            details,
+            ctx.accounts.config.default_fee_bps,
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
         market.vault = ctx.accounts.vault.key();
         market.vault_bump = ctx.bumps.vault;
-        market.resolver = ctx.accounts.creator.key(); // Deprecated, kept for compatibility
 
-        // Decentralized resolution fields
-        market.resolution_proposer = None;
-        market.resolution_bond = 0;
-        market.challenge_deadline = None;
-        market.is_finalized = false;
+        proposal.status = ProposalStatus::Approved;
+        proposal.market = Some(market.key());
+        proposal.reviewed_by = Some(ctx.accounts.curator.key());
 
-        msg!("Market created: {} (ID: {})", market.question, market_id);
+        msg!("Proposal {} approved as market {}", proposal.id, market_id);
+        Ok(())
+    }
+
+    /// Reject a proposal (MARKET CREATOR ROLE OR ADMIN)
+    pub fn reject_proposal(
+        ctx: Context<RejectProposal>,
+        _proposal_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::Creation)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.curator.key(), &ctx.accounts.curator_role),
+            MarketError::Unauthorized
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(proposal.status == ProposalStatus::Pending, MarketError::ProposalNotPending);
+
+        proposal.status = ProposalStatus::Rejected;
+        proposal.reviewed_by = Some(ctx.accounts.curator.key());
+
+        msg!("Proposal {} rejected", proposal.id);
         Ok(())
     }
 
-    /// Place a bet on a market (YES or NO)
+    /// Place a bet on one of a market's outcomes
     pub fn place_bet(
         ctx: Context<PlaceBet>,
         _market_id: u64,
         amount: u64,
-        prediction: bool, // true = YES, false = NO
+        outcome_index: u8, // Binary: 0 = YES, 1 = NO; scalar: 0 = LONG, 1 = SHORT; otherwise label or bucket
     ) -> Result<()> {
+        let config = &ctx.accounts.config;
         let market = &mut ctx.accounts.market;
         let bet = &mut ctx.accounts.bet;
         let clock = Clock::get()?;
 
+        config.require_not_paused(PauseScope::Betting)?;
+
         // Validations
+        require!(market.status != MarketStatus::Halted, MarketError::MarketHalted);
         require!(market.status == MarketStatus::Active, MarketError::MarketNotActive);
         require!(clock.unix_timestamp < market.end_time, MarketError::MarketEnded);
-        require!(amount >= 1_000_000, MarketError::BetTooSmall); // Min 1 USDC
-        require!(amount <= 10_000_000_000, MarketError::BetTooLarge); // Max 10,000 USDC
+        require!(amount >= config.min_bet, MarketError::BetTooSmall);
+        require!(amount <= config.max_bet, MarketError::BetTooLarge);
+        require!(outcome_index < market.outcome_count(), MarketError::InvalidOutcome);
 
-        // Calculate tokens received using simplified constant product AMM
-        let total_pool = market.yes_pool.checked_add(market.no_pool)
-            .ok_or(MarketError::MathOverflow)?;
-        let selected_pool = if prediction { market.yes_pool } else { market.no_pool };
+        let tokens_received = match market.market_type {
+            MarketType::Binary => {
+                let prediction = outcome_index == 0;
 
-        // Simplified AMM: tokens = (amount * opposite_pool) / (selected_pool + amount)
-        let opposite_pool = if prediction { market.no_pool } else { market.yes_pool };
-        let tokens_received = (amount as u128)
-            .checked_mul(opposite_pool as u128)
-            .ok_or(MarketError::MathOverflow)?
-            .checked_div((selected_pool as u128).checked_add(amount as u128).ok_or(MarketError::MathOverflow)?)
-            .ok_or(MarketError::MathOverflow)? as u64;
+                // Calculate tokens received using simplified constant product AMM
+                let selected_pool = if prediction { market.yes_pool } else { market.no_pool };
+
+                // Simplified AMM: tokens = (amount * opposite_pool) / (selected_pool + amount)
+                let opposite_pool = if prediction { market.no_pool } else { market.yes_pool };
+                (amount as u128)
+                    .checked_mul(opposite_pool as u128)
+                    .ok_or(MarketError::MathOverflow)?
+                    .checked_div((selected_pool as u128).checked_add(amount as u128).ok_or(MarketError::MathOverflow)?)
+                    .ok_or(MarketError::MathOverflow)? as u64
+            }
+            // Parimutuel: one share per USDC staked on the outcome or side
+            MarketType::Categorical(_) | MarketType::Buckets(_) | MarketType::Scalar(_) => amount,
+        };
 
         // Transfer USDC from user to vault
         let cpi_accounts = Transfer {
@@ -109,13 +455,8 @@ pub mod prediction_markets {
         token::transfer(cpi_ctx, amount)?;
 
         // Update market state
-        if prediction {
-            market.yes_pool = market.yes_pool.checked_add(amount).ok_or(MarketError::MathOverflow)?;
-            market.total_yes_bets = market.total_yes_bets.checked_add(amount).ok_or(MarketError::MathOverflow)?;
-        } else {
-            market.no_pool = market.no_pool.checked_add(amount).ok_or(MarketError::MathOverflow)?;
-            market.total_no_bets = market.total_no_bets.checked_add(amount).ok_or(MarketError::MathOverflow)?;
-        }
+        let bet_index = market.total_bets_count;
+        market.add_to_pool(outcome_index, amount)?;
         market.total_volume = market.total_volume.checked_add(amount).ok_or(MarketError::MathOverflow)?;
         market.total_bets_count = market.total_bets_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
 
@@ -124,10 +465,14 @@ pub mod prediction_markets {
         bet.user = ctx.accounts.user.key();
         bet.amount = amount;
         bet.tokens_received = tokens_received;
-        bet.prediction = prediction;
+        bet.outcome_index = outcome_index;
         bet.timestamp = clock.unix_timestamp;
         bet.claimed = false;
         bet.payout = 0;
+        bet.index = bet_index;
+        bet.yes_pool_after = market.yes_pool;
+        bet.no_pool_after = market.no_pool;
+        bet.outcome_pools_after = market.outcome_pools.clone();
 
         // Update user stats
         let user_stats = &mut ctx.accounts.user_stats;
@@ -146,430 +491,4458 @@ pub mod prediction_markets {
         user_stats.total_bets = user_stats.total_bets.checked_add(1).ok_or(MarketError::MathOverflow)?;
         user_stats.total_wagered = user_stats.total_wagered.checked_add(amount).ok_or(MarketError::MathOverflow)?;
 
-        msg!("Bet placed: {} USDC on {} for market {}",
-             amount, if prediction { "YES" } else { "NO" }, market.id);
+        msg!("Bet placed: {} USDC on outcome {} for market {}",
+             amount, outcome_index, market.id);
         Ok(())
     }
 
-    /// Resolve market (CREATOR-ONLY - instant resolution)
+    /// Propose a resolution without a bond (RESOLVER ROLE OR ADMIN)
+    /// Opens the challenge window like any other proposal. Passing `event_occurred_at`
+    /// resolves before end_time and turns bets placed after it into refunds; the
+    /// bets around the event must be passed so winners are paid from on-time pools
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
         _market_id: u64,
-        outcome: bool, // true = YES won, false = NO won
+        outcome: Outcome,
+        evidence: ResolutionEvidence,
+        event_occurred_at: Option<i64>,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
         let clock = Clock::get()?;
 
-        // Validations
-        require!(market.status == MarketStatus::Active, MarketError::MarketNotActive);
-        require!(clock.unix_timestamp >= market.end_time, MarketError::MarketNotEnded);
-        require!(ctx.accounts.creator.key() == market.creator, MarketError::NotCreator);
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.resolver.key(), &ctx.accounts.resolver_role),
+            MarketError::UnauthorizedResolver
+        );
 
-        // Instantly resolve the market
-        market.outcome = Some(outcome);
-        market.resolution_time = Some(clock.unix_timestamp);
-        market.status = MarketStatus::Resolved;
-        market.is_finalized = true;
+        if !market.parent_condition_met(ctx.accounts.parent_market.as_ref())? {
+            market.void(clock.unix_timestamp)?;
+            emit!(ResolutionFinalized {
+                market_id: market.id,
+                outcome: Outcome::Invalid,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Market {} voided: parent market missed the required outcome", market.id);
+            return Ok(());
+        }
 
-        msg!("Market {} instantly resolved by creator: {}",
-            market.id, if outcome { "YES" } else { "NO" });
+        if let Some(occurred_at) = event_occurred_at {
+            market.record_event_time(occurred_at, clock.unix_timestamp)?;
+            let market_key = market.key();
+            market.checkpoint_pools(
+                market_key,
+                occurred_at,
+                ctx.accounts.checkpoint_bet.as_deref(),
+                ctx.accounts.next_bet.as_deref(),
+            )?;
+        }
+        market.propose_resolution(
+            ctx.accounts.resolver.key(),
+            outcome,
+            evidence,
+            0,
+            ctx.accounts.config.challenge_period,
+            clock.unix_timestamp,
+        )?;
+
+        emit!(ResolutionProposed {
+            market_id: market.id,
+            proposer: ctx.accounts.resolver.key(),
+            outcome,
+            bond: 0,
+            challenge_deadline: market.challenge_deadline.unwrap_or_default(),
+            resolution_source: market.resolution_source.clone(),
+            evidence: market.evidence.clone(),
+            event_occurred_at: market.event_occurred_at,
+        });
+
+        msg!("Market {} resolution proposed by resolver: {:?}", market.id, outcome);
         Ok(())
     }
 
-    /// Claim winnings from a resolved market
-    pub fn claim_winnings(
-        ctx: Context<ClaimWinnings>,
+    /// Resolve a price feed market from its oracle (PERMISSIONLESS)
+    /// Objective outcome, so it is final without a challenge window
+    pub fn resolve_with_oracle(
+        ctx: Context<ResolveWithOracle>,
         _market_id: u64,
     ) -> Result<()> {
-        let market = &ctx.accounts.market;
-        let bet = &mut ctx.accounts.bet;
-        let user_stats = &mut ctx.accounts.user_stats;
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
 
-        require!(market.status == MarketStatus::Resolved, MarketError::MarketNotResolved);
-        require!(!bet.claimed, MarketError::AlreadyClaimed);
-        require!(bet.user == ctx.accounts.user.key(), MarketError::NotBetOwner);
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
 
-        let outcome = market.outcome.ok_or(MarketError::MarketNotResolved)?;
+        require!(
+            market.status == MarketStatus::Active || market.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        require!(clock.unix_timestamp >= market.end_time, MarketError::MarketNotEnded);
 
-        // Check if user won
-        if bet.prediction == outcome {
-            // Winner! Calculate payout
-            let winning_pool = if outcome { market.yes_pool } else { market.no_pool };
-            let losing_pool = if outcome { market.no_pool } else { market.yes_pool };
+        let condition = match &market.resolution_mode {
+            ResolutionMode::PriceFeed(condition) => condition.clone(),
+            _ => return err!(MarketError::WrongResolutionMode),
+        };
+        require!(ctx.accounts.price_feed.key() == condition.feed, MarketError::InvalidPriceFeed);
 
-            // Payout = (bet_amount / winning_pool) * losing_pool
-            let payout_ratio = (bet.amount as u128)
-                .checked_mul(losing_pool as u128)
-                .ok_or(MarketError::MathOverflow)?
-                .checked_div(winning_pool as u128)
-                .ok_or(MarketError::MathOverflow)? as u64;
+        let price = oracle::load_price(&condition, &ctx.accounts.price_feed)?;
+        let outcome = if condition.evaluate(&price, market.end_time)? {
+            Outcome::Yes
+        } else {
+            Outcome::No
+        };
 
-            // Add original bet back
-            let total_payout = bet.amount.checked_add(payout_ratio).ok_or(MarketError::MathOverflow)?;
+        market.outcome = Some(outcome);
+        market.resolver = condition.feed;
+        market.halted_at = None;
+        market.finalize(clock.unix_timestamp);
 
-            // Deduct 2% fee
-            let fee = total_payout.checked_mul(market.fee_percentage as u64)
-                .ok_or(MarketError::MathOverflow)?
-                .checked_div(100)
-                .ok_or(MarketError::MathOverflow)?;
-            let payout_after_fee = total_payout.checked_sub(fee).ok_or(MarketError::MathOverflow)?;
+        emit!(ResolutionFinalized {
+            market_id: market.id,
+            outcome,
+            timestamp: clock.unix_timestamp,
+        });
 
-            // Transfer winnings from vault to user
-            let market_id_bytes = market.id.to_le_bytes();
-            let vault_bump = &[market.vault_bump];
-            let seeds = &[
-                b"vault".as_ref(),
-                market_id_bytes.as_ref(),
-                vault_bump.as_ref(),
-            ];
-            let signer = &[&seeds[..]];
+        msg!("Market {} resolved by oracle: {:?} (price {} x 10^{})",
+            market.id, outcome, price.price, price.expo);
+        Ok(())
+    }
 
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.vault.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            token::transfer(cpi_ctx, payout_after_fee)?;
+    /// Attest to the outcome of a committee market (COMMITTEE MEMBER ONLY)
+    /// Resolves once `threshold` members agree; a conflicting attestation
+    /// sends the market to a juror dispute instead
+    pub fn submit_attestation(
+        ctx: Context<SubmitAttestation>,
+        market_id: u64,
+        outcome: Outcome,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let resolver = ctx.accounts.resolver.key();
+        let clock = Clock::get()?;
 
-            bet.payout = payout_after_fee;
-            bet.claimed = true;
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
 
-            // Update user stats
-            user_stats.total_won = user_stats.total_won.checked_add(payout_after_fee).ok_or(MarketError::MathOverflow)?;
-            user_stats.win_count = user_stats.win_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
-            user_stats.current_streak = user_stats.current_streak.checked_add(1).ok_or(MarketError::MathOverflow)?;
-            if user_stats.current_streak > user_stats.best_streak {
-                user_stats.best_streak = user_stats.current_streak;
-            }
+        require!(
+            market.status == MarketStatus::Active || market.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        require!(clock.unix_timestamp >= market.end_time, MarketError::MarketNotEnded);
 
-            let profit = (payout_after_fee as i64).checked_sub(bet.amount as i64).ok_or(MarketError::MathOverflow)?;
-            user_stats.net_profit = user_stats.net_profit.checked_add(profit).ok_or(MarketError::MathOverflow)?;
+        let committee = match &market.resolution_mode {
+            ResolutionMode::Committee(committee) => committee.clone(),
+            _ => return err!(MarketError::WrongResolutionMode),
+        };
+        require!(committee.resolvers.contains(&resolver), MarketError::NotCommitteeMember);
+        market.require_valid_outcome(outcome)?;
 
-            msg!("User won {} USDC!", payout_after_fee);
-        } else {
-            // Loser - no payout
-            bet.payout = 0;
-            bet.claimed = true;
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.market = market.key();
+        attestation.resolver = resolver;
+        attestation.outcome = outcome;
+        attestation.timestamp = clock.unix_timestamp;
+        attestation.bump = ctx.bumps.attestation;
 
-            user_stats.total_lost = user_stats.total_lost.checked_add(bet.amount).ok_or(MarketError::MathOverflow)?;
-            user_stats.loss_count = user_stats.loss_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
-            user_stats.current_streak = 0;
+        emit!(AttestationSubmitted {
+            market_id,
+            resolver,
+            outcome,
+            timestamp: clock.unix_timestamp,
+        });
 
-            let loss = -(bet.amount as i64);
-            user_stats.net_profit = user_stats.net_profit.checked_add(loss).ok_or(MarketError::MathOverflow)?;
+        // Dispute accounts are only created for a conflicting attestation, so an
+        // agreeing member cannot squat the market's dispute PDA
+        let no_dispute_accounts = ctx.accounts.dispute.is_none() && ctx.accounts.dispute_vault.is_none();
 
-            msg!("User lost the bet");
+        match market.outcome {
+            // First attestation sets the outcome the rest have to agree with
+            None => {
+                require!(no_dispute_accounts, MarketError::UnexpectedDisputeAccounts);
+                market.outcome = Some(outcome);
+                market.resolution_proposer = Some(resolver);
+                market.attestation_count = 1;
+            }
+            Some(leading) if leading == outcome => {
+                require!(no_dispute_accounts, MarketError::UnexpectedDisputeAccounts);
+                market.attestation_count = market.attestation_count
+                    .checked_add(1)
+                    .ok_or(MarketError::MathOverflow)?;
+            }
+            Some(_) => {
+                let dispute = ctx.accounts.dispute
+                    .as_mut()
+                    .ok_or(MarketError::MissingDisputeAccounts)?;
+                require!(ctx.accounts.dispute_vault.is_some(), MarketError::MissingDisputeAccounts);
+
+                dispute.open(
+                    market.key(),
+                    market,
+                    outcome,
+                    &ctx.accounts.config,
+                    clock.unix_timestamp,
+                )?;
+                dispute.bump = ctx.bumps.dispute.ok_or(MarketError::MissingDisputeAccounts)?;
+                dispute.vault_bump = ctx.bumps.dispute_vault.ok_or(MarketError::MissingDisputeAccounts)?;
+
+                market.status = MarketStatus::Disputed;
+                market.challenger = Some(resolver);
+                market.halted_at = None;
+
+                emit!(ResolutionChallenged {
+                    market_id,
+                    challenger: resolver,
+                    bond: 0,
+                    timestamp: clock.unix_timestamp,
+                });
+
+                msg!("Committee disagrees on market {}, dispute opened", market_id);
+                return Ok(());
+            }
         }
 
+        if market.attestation_count >= committee.threshold {
+            market.resolver = resolver;
+            market.halted_at = None;
+            market.finalize(clock.unix_timestamp);
+
+            emit!(ResolutionFinalized {
+                market_id,
+                outcome,
+                timestamp: clock.unix_timestamp,
+            });
+
+            msg!("Market {} resolved by committee: {:?}", market_id, outcome);
+        } else {
+            msg!("Attestation {}/{} for {:?} on market {}",
+                market.attestation_count, committee.threshold, outcome, market_id);
+        }
         Ok(())
     }
 
-    /// Cancel market (only creator, only if no bets placed)
-    pub fn cancel_market(
-        ctx: Context<CancelMarket>,
+    /// Propose a resolution backed by the configured bond (PERMISSIONLESS)
+    /// The bond is returned once the challenge window passes unchallenged
+    pub fn propose_resolution(
+        ctx: Context<ProposeResolution>,
+        _market_id: u64,
+        outcome: Outcome,
+        evidence: ResolutionEvidence,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
 
-        require!(ctx.accounts.creator.key() == market.creator, MarketError::NotCreator);
-        require!(market.total_bets_count == 0, MarketError::HasBets);
-        require!(market.status == MarketStatus::Active, MarketError::MarketNotActive);
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
 
-        market.status = MarketStatus::Cancelled;
+        // Nothing to bond on when the condition already failed
+        if !market.parent_condition_met(ctx.accounts.parent_market.as_ref())? {
+            market.void(clock.unix_timestamp)?;
+            emit!(ResolutionFinalized {
+                market_id: market.id,
+                outcome: Outcome::Invalid,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Market {} voided: parent market missed the required outcome", market.id);
+            return Ok(());
+        }
 
-        msg!("Market {} cancelled", market.id);
+        let bond = ctx.accounts.config.resolution_bond;
+        market.propose_resolution(
+            ctx.accounts.proposer.key(),
+            outcome,
+            evidence,
+            bond,
+            ctx.accounts.config.challenge_period,
+            clock.unix_timestamp,
+        )?;
+
+        if bond > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.proposer_token_account.to_account_info(),
+                to: ctx.accounts.bond_escrow.to_account_info(),
+                authority: ctx.accounts.proposer.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, bond)?;
+        }
+
+        emit!(ResolutionProposed {
+            market_id: market.id,
+            proposer: ctx.accounts.proposer.key(),
+            outcome,
+            bond,
+            challenge_deadline: market.challenge_deadline.unwrap_or_default(),
+            resolution_source: market.resolution_source.clone(),
+            evidence: market.evidence.clone(),
+            event_occurred_at: None,
+        });
+
+        msg!("Market {} resolution proposed: {:?} (bond {})", market.id, outcome, bond);
         Ok(())
     }
 
-    /// Withdraw accumulated fees from a market's vault (ADMIN ONLY)
-    /// Fees are the surplus in the vault beyond the yes_pool + no_pool collateral
-    pub fn withdraw_fees(
-        ctx: Context<WithdrawFees>,
-        market_id: u64,
+    /// Contest a proposed resolution during the challenge window (PERMISSIONLESS)
+    /// The challenger matches the proposer's bond, or the configured bond if
+    /// the proposal came from a resolver, and a juror vote is opened
+    pub fn challenge_resolution(
+        ctx: Context<ChallengeResolution>,
+        _market_id: u64,
+        counter_outcome: Outcome,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        require!(market.status == MarketStatus::ResolutionProposed, MarketError::NoResolutionProposed);
+        let deadline = market.challenge_deadline.ok_or(MarketError::NoResolutionProposed)?;
+        require!(clock.unix_timestamp < deadline, MarketError::ChallengePeriodEnded);
+
+        let proposed_outcome = market.outcome.ok_or(MarketError::NoResolutionProposed)?;
+        require!(counter_outcome != proposed_outcome, MarketError::SameOutcome);
+        market.require_valid_outcome(counter_outcome)?;
+
+        let bond = if market.resolution_bond > 0 {
+            market.resolution_bond
+        } else {
+            ctx.accounts.config.resolution_bond
+        };
+
+        if bond > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.challenger_token_account.to_account_info(),
+                to: ctx.accounts.bond_escrow.to_account_info(),
+                authority: ctx.accounts.challenger.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, bond)?;
+        }
+
+        market.status = MarketStatus::Disputed;
+        market.challenger = Some(ctx.accounts.challenger.key());
+        market.challenge_bond = bond;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.open(
+            market.key(),
+            market,
+            counter_outcome,
+            &ctx.accounts.config,
+            clock.unix_timestamp,
+        )?;
+        dispute.bump = ctx.bumps.dispute;
+        dispute.vault_bump = ctx.bumps.dispute_vault;
+
+        emit!(ResolutionChallenged {
+            market_id: market.id,
+            challenger: ctx.accounts.challenger.key(),
+            bond,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} resolution challenged with {:?} (bond {})", market.id, counter_outcome, bond);
+        Ok(())
+    }
+
+    /// Finalize an unchallenged resolution once the window has passed (PERMISSIONLESS)
+    /// Returns the proposer's bond
+    pub fn finalize_resolution(
+        ctx: Context<FinalizeResolution>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        require!(market.status == MarketStatus::ResolutionProposed, MarketError::NoResolutionProposed);
+        let deadline = market.challenge_deadline.ok_or(MarketError::NoResolutionProposed)?;
+        require!(clock.unix_timestamp >= deadline, MarketError::ChallengePeriodActive);
+
+        let bond = market.resolution_bond;
+        if bond > 0 {
+            let proposer_token_account = ctx.accounts.proposer_token_account
+                .as_ref()
+                .ok_or(MarketError::MissingBondTokenAccount)?;
+            require!(
+                Some(proposer_token_account.owner) == market.resolution_proposer,
+                MarketError::InvalidBondDestination
+            );
+
+            let escrow_bump = &[ctx.bumps.bond_escrow];
+            let seeds = &[
+                b"bond_escrow".as_ref(),
+                escrow_bump.as_ref(),
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_escrow.to_account_info(),
+                to: proposer_token_account.to_account_info(),
+                authority: ctx.accounts.bond_escrow.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, bond)?;
+        }
+
+        market.resolution_bond = 0;
+        market.finalize(clock.unix_timestamp);
+
+        emit!(ResolutionFinalized {
+            market_id: market.id,
+            outcome: market.outcome.ok_or(MarketError::NoResolutionProposed)?,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} resolution finalized", market.id);
+        Ok(())
+    }
+
+    /// Void a market nobody resolved before its resolution deadline (PERMISSIONLESS)
+    /// Resolves it as Invalid so every bettor can reclaim their stake
+    pub fn void_expired_market(
+        ctx: Context<VoidExpiredMarket>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        // Proposals and disputes in flight settle through their own instructions
+        require!(
+            market.status == MarketStatus::Active || market.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp >= market.resolution_deadline,
+            MarketError::ResolutionDeadlineNotReached
+        );
+        // Reporting markets always settle, voiding when nobody reports
+        require!(
+            !matches!(market.resolution_mode, ResolutionMode::Reporting(_)),
+            MarketError::WrongResolutionMode
+        );
+
+        market.void(clock.unix_timestamp)?;
+
+        emit!(MarketVoided {
+            market_id: market.id,
+            resolution_deadline: market.resolution_deadline,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} voided after its resolution deadline", market.id);
+        Ok(())
+    }
+
+    /// Void an ambiguous market that already has bets (ADMIN ONLY)
+    /// Every bet is refunded and the creation bond is slashed
+    pub fn void_market(
+        ctx: Context<VoidMarket>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, MarketError::Unauthorized);
+
+        market.void(clock.unix_timestamp)?;
+        market.admin_voided = true;
+
+        emit!(ResolutionFinalized {
+            market_id: market.id,
+            outcome: Outcome::Invalid,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} voided by admin", market.id);
+        Ok(())
+    }
+
+    /// Stake on a disputed market with a hidden vote (PERMISSIONLESS)
+    /// commitment = hash(borsh(outcome) || salt || juror || market_id), revealed after the commit period
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        _market_id: u64,
+        commitment: [u8; 32],
+        stake: u64,
     ) -> Result<()> {
-        // Only admin can withdraw fees
-        require!(
-            ctx.accounts.admin.key().to_string() == ADMIN_PUBKEY,
-            MarketError::Unauthorized
-        );
-
         let market = &ctx.accounts.market;
-        let vault = &ctx.accounts.vault;
+        let dispute = &mut ctx.accounts.dispute;
+        let juror = ctx.accounts.juror.key();
+        let clock = Clock::get()?;
 
-        // Market must be resolved to withdraw fees
-        require!(market.status == MarketStatus::Resolved, MarketError::MarketNotResolved);
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        require!(market.status == MarketStatus::Disputed, MarketError::MarketNotDisputed);
+        require!(clock.unix_timestamp < dispute.commit_deadline, MarketError::CommitPeriodEnded);
+        require!(stake >= ctx.accounts.config.min_juror_stake && stake > 0, MarketError::StakeTooSmall);
+        require!(
+            Some(juror) != market.resolution_proposer && Some(juror) != market.challenger,
+            MarketError::ConflictedJuror
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.juror_token_account.to_account_info(),
+            to: ctx.accounts.dispute_vault.to_account_info(),
+            authority: ctx.accounts.juror.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, stake)?;
+
+        let vote = &mut ctx.accounts.juror_vote;
+        vote.dispute = dispute.key();
+        vote.juror = juror;
+        vote.commitment = commitment;
+        vote.stake = stake;
+        vote.vote = None;
+        vote.claimed = false;
+        vote.bump = ctx.bumps.juror_vote;
+
+        dispute.total_staked = dispute.total_staked.checked_add(stake).ok_or(MarketError::MathOverflow)?;
+        dispute.juror_count = dispute.juror_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+
+        msg!("Juror {} committed {} on market {}", juror, stake, market.id);
+        Ok(())
+    }
+
+    /// Reveal a committed vote (JUROR ONLY)
+    /// Unrevealed stakes are forfeited to the majority
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        market_id: u64,
+        outcome: Outcome,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let vote = &mut ctx.accounts.juror_vote;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
 
-        // Calculate fees: vault balance minus the virtual liquidity pools
-        // Note: yes_pool and no_pool include virtual liquidity (1000 USDC each initially)
-        // The actual collateral is total_yes_bets + total_no_bets
-        // After resolution and claims, remaining balance is fees
-        let vault_balance = vault.amount;
-        let collateral = market.total_yes_bets
-            .checked_add(market.total_no_bets)
+        require!(clock.unix_timestamp >= dispute.commit_deadline, MarketError::CommitPeriodActive);
+        require!(clock.unix_timestamp < dispute.reveal_deadline, MarketError::RevealPeriodEnded);
+        require!(vote.vote.is_none(), MarketError::AlreadyRevealed);
+
+        // Binding the juror and market stops a commitment being copied and replayed
+        let encoded = outcome.try_to_vec()?;
+        let hash = hashv(&[
+            encoded.as_ref(),
+            salt.as_ref(),
+            vote.juror.as_ref(),
+            market_id.to_le_bytes().as_ref(),
+        ]);
+        require!(hash.to_bytes() == vote.commitment, MarketError::InvalidReveal);
+
+        // Only the two contested outcomes can be voted for
+        if outcome == dispute.proposed_outcome {
+            dispute.proposed_stake = dispute.proposed_stake.checked_add(vote.stake).ok_or(MarketError::MathOverflow)?;
+        } else if outcome == dispute.counter_outcome {
+            dispute.counter_stake = dispute.counter_stake.checked_add(vote.stake).ok_or(MarketError::MathOverflow)?;
+        } else {
+            return err!(MarketError::InvalidVote);
+        }
+        vote.vote = Some(outcome);
+
+        msg!("Juror {} revealed {:?}", vote.juror, outcome);
+        Ok(())
+    }
+
+    /// Tally a dispute once the reveal period is over (PERMISSIONLESS)
+    /// The stake-weighted majority decides the outcome (a tie keeps the proposed
+    /// outcome). The winning bond holder gets their bond back; the loser's bond
+    /// and every other juror stake become the majority's reward pool
+    pub fn settle_dispute(
+        ctx: Context<SettleDispute>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let dispute = &mut ctx.accounts.dispute;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        require!(market.status == MarketStatus::Disputed, MarketError::MarketNotDisputed);
+        require!(clock.unix_timestamp >= dispute.reveal_deadline, MarketError::RevealPeriodActive);
+
+        let outcome = dispute.tally();
+        let (winner, winner_bond, loser_bond) = if outcome == dispute.proposed_outcome {
+            (market.resolution_proposer, market.resolution_bond, market.challenge_bond)
+        } else {
+            (market.challenger, market.challenge_bond, market.resolution_bond)
+        };
+        let winner = winner.ok_or(MarketError::NoResolutionProposed)?;
+        require!(
+            ctx.accounts.winner_token_account.owner == winner,
+            MarketError::InvalidBondDestination
+        );
+
+        let winning_stake = dispute.stake_for(outcome);
+        let forfeited_stake = dispute.total_staked
+            .checked_sub(winning_stake)
             .ok_or(MarketError::MathOverflow)?;
 
-        // After winners claim, remaining funds in vault are fees
-        // This should be called after all winners have claimed
-        let fees = vault_balance.saturating_sub(0); // All remaining is fees after resolution
+        let escrow_bump = &[ctx.bumps.bond_escrow];
+        let escrow_seeds = &[
+            b"bond_escrow".as_ref(),
+            escrow_bump.as_ref(),
+        ];
+        let escrow_signer = &[&escrow_seeds[..]];
 
-        require!(fees > 0, MarketError::NoFeesToWithdraw);
+        // Nobody backed the winning side: the winner takes everything at stake
+        let (winner_payout, reward_pool) = if winning_stake == 0 {
+            (winner_bond.checked_add(loser_bond).ok_or(MarketError::MathOverflow)?, 0)
+        } else {
+            (winner_bond, loser_bond.checked_add(forfeited_stake).ok_or(MarketError::MathOverflow)?)
+        };
+
+        if winner_payout > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_escrow.to_account_info(),
+                to: ctx.accounts.winner_token_account.to_account_info(),
+                authority: ctx.accounts.bond_escrow.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, escrow_signer);
+            token::transfer(cpi_ctx, winner_payout)?;
+        }
+
+        if winning_stake > 0 && loser_bond > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_escrow.to_account_info(),
+                to: ctx.accounts.dispute_vault.to_account_info(),
+                authority: ctx.accounts.bond_escrow.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, escrow_signer);
+            token::transfer(cpi_ctx, loser_bond)?;
+        }
+
+        if winning_stake == 0 && forfeited_stake > 0 {
+            let market_id_bytes = market.id.to_le_bytes();
+            let vault_bump = &[dispute.vault_bump];
+            let seeds = &[
+                b"dispute_vault".as_ref(),
+                market_id_bytes.as_ref(),
+                vault_bump.as_ref(),
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.dispute_vault.to_account_info(),
+                to: ctx.accounts.winner_token_account.to_account_info(),
+                authority: ctx.accounts.dispute_vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, forfeited_stake)?;
+        }
+
+        dispute.settled = true;
+        dispute.winning_outcome = Some(outcome);
+        dispute.reward_pool = reward_pool;
+
+        market.outcome = Some(outcome);
+        market.resolver = dispute.key();
+        market.resolution_bond = 0;
+        market.challenge_bond = 0;
+        market.finalize(clock.unix_timestamp);
+
+        emit!(DisputeSettled {
+            market_id: market.id,
+            outcome,
+            winning_stake,
+            reward_pool,
+        });
+        emit!(ResolutionFinalized {
+            market_id: market.id,
+            outcome,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Dispute on market {} settled by jurors: {:?} ({} vs {})",
+            market.id, outcome, dispute.proposed_stake, dispute.counter_stake);
+        Ok(())
+    }
+
+    /// Collect a majority juror's stake plus their share of the reward pool (JUROR ONLY)
+    pub fn claim_juror_reward(
+        ctx: Context<ClaimJurorReward>,
+        market_id: u64,
+    ) -> Result<()> {
+        let dispute = &ctx.accounts.dispute;
+        let vote = &mut ctx.accounts.juror_vote;
+
+        ctx.accounts.config.require_not_paused(PauseScope::Claiming)?;
+
+        require!(dispute.settled, MarketError::DisputeNotSettled);
+        require!(!vote.claimed, MarketError::AlreadyClaimed);
+        require!(
+            vote.vote.is_some() && vote.vote == dispute.winning_outcome,
+            MarketError::NotWinningJuror
+        );
+
+        let payout = dispute.juror_payout(vote.stake)?;
+        vote.claimed = true;
 
-        // Transfer fees to admin
         let market_id_bytes = market_id.to_le_bytes();
-        let vault_bump = &[market.vault_bump];
+        let vault_bump = &[dispute.vault_bump];
         let seeds = &[
-            b"vault".as_ref(),
+            b"dispute_vault".as_ref(),
             market_id_bytes.as_ref(),
             vault_bump.as_ref(),
         ];
         let signer = &[&seeds[..]];
 
         let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.admin_token_account.to_account_info(),
-            authority: ctx.accounts.vault.to_account_info(),
+            from: ctx.accounts.dispute_vault.to_account_info(),
+            to: ctx.accounts.juror_token_account.to_account_info(),
+            authority: ctx.accounts.dispute_vault.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, fees)?;
+        token::transfer(cpi_ctx, payout)?;
+
+        msg!("Juror {} claimed {} from dispute on market {}", vote.juror, payout, market_id);
+        Ok(())
+    }
+
+    /// Stake on the outcome of a reporting market (PERMISSIONLESS)
+    /// Open from end_time until the market's reporting period runs out
+    pub fn submit_report(
+        ctx: Context<SubmitReport>,
+        _market_id: u64,
+        outcome: Outcome,
+        stake: u64,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let reporting = &mut ctx.accounts.reporting;
+        let reporter = ctx.accounts.reporter.key();
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        let params = match &market.resolution_mode {
+            ResolutionMode::Reporting(params) => params.clone(),
+            _ => return err!(MarketError::WrongResolutionMode),
+        };
+        require!(
+            market.status == MarketStatus::Active || market.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        require!(clock.unix_timestamp >= market.end_time, MarketError::MarketNotEnded);
+
+        // First report opens the round
+        if reporting.market == Pubkey::default() {
+            reporting.open(market.key(), market, &params)?;
+            reporting.bump = ctx.bumps.reporting;
+            reporting.vault_bump = ctx.bumps.reporting_vault;
+        }
+        require!(clock.unix_timestamp < reporting.reporting_deadline, MarketError::ReportingPeriodEnded);
+        require!(stake >= ctx.accounts.config.min_juror_stake && stake > 0, MarketError::StakeTooSmall);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reporter_token_account.to_account_info(),
+            to: ctx.accounts.reporting_vault.to_account_info(),
+            authority: ctx.accounts.reporter.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, stake)?;
+
+        let report = &mut ctx.accounts.report;
+        report.reporting = reporting.key();
+        report.reporter = reporter;
+        report.outcome = outcome;
+        report.stake = stake;
+        report.claimed = false;
+        report.bump = ctx.bumps.report;
+
+        reporting.add_stake(outcome, stake)?;
+
+        emit!(ReportSubmitted {
+            market_id: market.id,
+            reporter,
+            outcome,
+            stake,
+        });
+
+        msg!("Reporter {} staked {} on {:?} for market {}", reporter, stake, outcome, market.id);
+        Ok(())
+    }
+
+    /// Resolve a reporting market once its reporting period is over (PERMISSIONLESS)
+    /// The stake-weighted majority decides the outcome; a tie or no reports voids it.
+    /// A market voided or cancelled by an admin first settles the round as a full refund
+    pub fn settle_reports(
+        ctx: Context<SettleReports>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let reporting = &mut ctx.accounts.reporting;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+
+        let params = match &market.resolution_mode {
+            ResolutionMode::Reporting(params) => params.clone(),
+            _ => return err!(MarketError::WrongResolutionMode),
+        };
+
+        // Nobody reported: the round is created here so it can be settled
+        if reporting.market == Pubkey::default() {
+            reporting.open(market.key(), market, &params)?;
+            reporting.bump = ctx.bumps.reporting;
+        }
+
+        if market.status == MarketStatus::Resolved || market.status == MarketStatus::Cancelled {
+            require!(!reporting.settled, MarketError::ReportsAlreadySettled);
+            reporting.refund_all();
+
+            msg!("Market {} closed before its reports settled, refunding reporters", market.id);
+            return Ok(());
+        }
+        require!(
+            market.status == MarketStatus::Active || market.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp >= reporting.reporting_deadline,
+            MarketError::ReportingPeriodActive
+        );
+
+        let outcome = reporting.settle()?;
+
+        market.outcome = Some(outcome);
+        market.resolver = reporting.key();
+        market.halted_at = None;
+        market.finalize(clock.unix_timestamp);
+
+        emit!(ReportsSettled {
+            market_id: market.id,
+            outcome,
+            winning_stake: reporting.stake_for(outcome),
+            reward_pool: reporting.reward_pool,
+        });
+        emit!(ResolutionFinalized {
+            market_id: market.id,
+            outcome,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} resolved by reporters: {:?}", market.id, outcome);
+        Ok(())
+    }
+
+    /// Collect a report's stake after settlement (REPORTER ONLY)
+    /// Majority reporters share the slashed stake, minority reporters lose the slashed part
+    pub fn claim_report(
+        ctx: Context<ClaimReport>,
+        market_id: u64,
+    ) -> Result<()> {
+        let reporting = &ctx.accounts.reporting;
+        let report = &mut ctx.accounts.report;
+
+        ctx.accounts.config.require_not_paused(PauseScope::Claiming)?;
+
+        require!(reporting.settled, MarketError::ReportsNotSettled);
+        require!(!report.claimed, MarketError::AlreadyClaimed);
+
+        let payout = reporting.reporter_payout(report.outcome, report.stake)?;
+        report.claimed = true;
+
+        if payout > 0 {
+            let market_id_bytes = market_id.to_le_bytes();
+            let vault_bump = &[reporting.vault_bump];
+            let seeds = &[
+                b"reporting_vault".as_ref(),
+                market_id_bytes.as_ref(),
+                vault_bump.as_ref(),
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.reporting_vault.to_account_info(),
+                to: ctx.accounts.reporter_token_account.to_account_info(),
+                authority: ctx.accounts.reporting_vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, payout)?;
+        }
+
+        msg!("Reporter {} claimed {} from market {}", report.reporter, payout, market_id);
+        Ok(())
+    }
+
+    /// Set the parlay house's payout, liability and per-leg caps (ADMIN ONLY)
+    /// Creates the house and its vault on first use
+    pub fn configure_parlay_house(
+        ctx: Context<ConfigureParlayHouse>,
+        max_payout: u64,
+        max_liability: u64,
+        max_leg_odds_bps: u64,
+        min_leg_pool: u64,
+    ) -> Result<()> {
+        require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, MarketError::Unauthorized);
+        require!(
+            max_payout > 0 && max_payout <= max_liability && max_leg_odds_bps > BPS_DENOMINATOR,
+            MarketError::InvalidParlayLimits
+        );
+
+        let house = &mut ctx.accounts.parlay_house;
+        house.max_payout = max_payout;
+        house.max_liability = max_liability;
+        house.max_leg_odds_bps = max_leg_odds_bps;
+        house.min_leg_pool = min_leg_pool;
+        house.bump = ctx.bumps.parlay_house;
+        house.vault_bump = ctx.bumps.parlay_vault;
+
+        msg!(
+            "Parlay house limits set: max payout {}, max liability {}, max leg odds {} bps, min leg pool {}",
+            max_payout,
+            max_liability,
+            max_leg_odds_bps,
+            min_leg_pool
+        );
+        Ok(())
+    }
+
+    /// Add collateral backing parlay payouts (PERMISSIONLESS)
+    pub fn fund_parlay_house(
+        ctx: Context<FundParlayHouse>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        require!(amount > 0, MarketError::InvalidFundingAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.parlay_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("Parlay house funded with {} by {}", amount, ctx.accounts.funder.key());
+        Ok(())
+    }
+
+    /// Withdraw house collateral not reserved for open parlays (TREASURER ROLE OR ADMIN)
+    pub fn withdraw_parlay_house(
+        ctx: Context<WithdrawParlayHouse>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::FeeWithdrawal)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.treasurer.key(), &ctx.accounts.treasurer_role),
+            MarketError::Unauthorized
+        );
+
+        let free_balance = ctx.accounts.parlay_vault.amount
+            .checked_sub(ctx.accounts.parlay_house.outstanding_liability)
+            .ok_or(MarketError::MathOverflow)?;
+        require!(amount > 0 && amount <= free_balance, MarketError::ParlayLiabilityExceeded);
+
+        let vault_bump = &[ctx.accounts.parlay_house.vault_bump];
+        let seeds = &[b"parlay_vault".as_ref(), vault_bump.as_ref()];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.parlay_vault.to_account_info(),
+            to: ctx.accounts.fee_recipient_token_account.to_account_info(),
+            authority: ctx.accounts.parlay_vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("Withdrawn {} from the parlay house to fee recipient", amount);
+        Ok(())
+    }
+
+    /// Bet a single stake on several markets at once (PERMISSIONLESS)
+    /// Leg markets are passed as remaining accounts in the order of `outcome_indexes`;
+    /// each leg's odds are locked from its pools now and the house reserves the payout.
+    /// Thin markets and long-shot legs are refused so a bettor cannot skew a pool
+    /// to lock in odds the house would pay on
+    pub fn place_parlay(
+        ctx: Context<PlaceParlay>,
+        _parlay_id: u64,
+        stake: u64,
+        outcome_indexes: Vec<u8>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let house = &mut ctx.accounts.parlay_house;
+        let clock = Clock::get()?;
+
+        config.require_not_paused(PauseScope::Betting)?;
+
+        require!(stake >= config.min_bet, MarketError::BetTooSmall);
+        require!(stake <= config.max_bet, MarketError::BetTooLarge);
+        require!(
+            outcome_indexes.len() >= 2 && outcome_indexes.len() <= MAX_PARLAY_LEGS,
+            MarketError::InvalidParlayLegs
+        );
+        require!(
+            ctx.remaining_accounts.len() == outcome_indexes.len(),
+            MarketError::InvalidParlayLegs
+        );
+
+        let mut legs: Vec<ParlayLeg> = Vec::with_capacity(outcome_indexes.len());
+        for (info, outcome_index) in ctx.remaining_accounts.iter().zip(outcome_indexes) {
+            let market = Market::load(info)?;
+            require!(market.status != MarketStatus::Halted, MarketError::MarketHalted);
+            require!(market.status == MarketStatus::Active, MarketError::MarketNotActive);
+            require!(clock.unix_timestamp < market.end_time, MarketError::MarketEnded);
+            require!(
+                legs.iter().all(|leg| leg.market != info.key()),
+                MarketError::InvalidParlayLegs
+            );
+            require!(market.total_volume >= house.min_leg_pool, MarketError::ParlayLegPoolTooSmall);
+
+            let odds_bps = market.odds_bps(outcome_index)?;
+            require!(odds_bps <= house.max_leg_odds_bps, MarketError::ParlayLegOddsTooHigh);
+
+            legs.push(ParlayLeg {
+                market: info.key(),
+                outcome_index,
+                odds_bps,
+            });
+        }
+
+        let potential_payout = Parlay::payout_for(stake, legs.iter().map(|leg| leg.odds_bps))?;
+        require!(potential_payout <= house.max_payout, MarketError::ParlayPayoutTooLarge);
+
+        // Every open parlay must stay covered by the vault, including this stake
+        let outstanding = house.outstanding_liability
+            .checked_add(potential_payout)
+            .ok_or(MarketError::MathOverflow)?;
+        let backing = ctx.accounts.parlay_vault.amount
+            .checked_add(stake)
+            .ok_or(MarketError::MathOverflow)?;
+        require!(
+            outstanding <= house.max_liability && outstanding <= backing,
+            MarketError::ParlayLiabilityExceeded
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.parlay_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, stake)?;
+
+        house.outstanding_liability = outstanding;
+
+        let parlay = &mut ctx.accounts.parlay;
+        parlay.user = ctx.accounts.user.key();
+        parlay.stake = stake;
+        parlay.legs = legs;
+        parlay.potential_payout = potential_payout;
+        parlay.placed_at = clock.unix_timestamp;
+        parlay.status = ParlayStatus::Open;
+        parlay.payout = 0;
+        parlay.bump = ctx.bumps.parlay;
+
+        emit!(ParlayPlaced {
+            parlay: parlay.key(),
+            user: parlay.user,
+            stake,
+            legs: parlay.legs.len() as u8,
+            potential_payout,
+        });
+
+        msg!(
+            "Parlay of {} legs placed: stake {}, potential payout {}",
+            parlay.legs.len(),
+            stake,
+            potential_payout
+        );
+        Ok(())
+    }
+
+    /// Settle a parlay from its leg markets' outcomes (PERMISSIONLESS)
+    /// Leg markets are passed as remaining accounts in placement order. One losing leg
+    /// loses the parlay straight away; voided legs count at even odds
+    pub fn settle_parlay(
+        ctx: Context<SettleParlay>,
+        _parlay_id: u64,
+    ) -> Result<()> {
+        let house = &mut ctx.accounts.parlay_house;
+        let parlay = &mut ctx.accounts.parlay;
+
+        ctx.accounts.config.require_not_paused(PauseScope::Claiming)?;
+
+        require!(parlay.status == ParlayStatus::Open, MarketError::ParlayAlreadySettled);
+        require!(
+            ctx.remaining_accounts.len() == parlay.legs.len(),
+            MarketError::InvalidParlayLegs
+        );
+
+        let mut results = Vec::with_capacity(parlay.legs.len());
+        for (info, leg) in ctx.remaining_accounts.iter().zip(parlay.legs.iter()) {
+            require_keys_eq!(info.key(), leg.market, MarketError::InvalidParlayLegs);
+            let market = Market::load(info)?;
+            results.push(market.leg_result(leg.outcome_index, parlay.placed_at));
+        }
+
+        let payout = if results.contains(&LegResult::Lost) {
+            0
+        } else {
+            require!(!results.contains(&LegResult::Open), MarketError::ParlayLegsOpen);
+            let odds = parlay.legs.iter().zip(results.iter()).map(|(leg, result)| {
+                if *result == LegResult::Won { leg.odds_bps } else { BPS_DENOMINATOR }
+            });
+            Parlay::payout_for(parlay.stake, odds)?
+        };
+
+        house.outstanding_liability = house.outstanding_liability
+            .checked_sub(parlay.potential_payout)
+            .ok_or(MarketError::MathOverflow)?;
+        parlay.payout = payout;
+        parlay.status = if payout == 0 {
+            ParlayStatus::Lost
+        } else if results.iter().all(|result| *result == LegResult::Void) {
+            ParlayStatus::Refunded
+        } else {
+            ParlayStatus::Won
+        };
+
+        if payout > 0 {
+            let vault_bump = &[house.vault_bump];
+            let seeds = &[b"parlay_vault".as_ref(), vault_bump.as_ref()];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.parlay_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.parlay_vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, payout)?;
+        }
+
+        emit!(ParlaySettled {
+            parlay: parlay.key(),
+            user: parlay.user,
+            status: parlay.status,
+            payout,
+        });
+
+        msg!("Parlay {} settled as {:?}, paid {}", parlay.key(), parlay.status, payout);
+        Ok(())
+    }
+
+    /// Suspend trading on a market without ending it (RESOLVER ROLE OR ADMIN)
+    pub fn halt_market(
+        ctx: Context<SetMarketHalt>,
+        _market_id: u64,
+        reason_code: u16,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.resolver.key(), &ctx.accounts.resolver_role),
+            MarketError::UnauthorizedResolver
+        );
+        require!(market.status == MarketStatus::Active, MarketError::MarketNotActive);
+
+        market.status = MarketStatus::Halted;
+        market.halt_reason = reason_code;
+        market.halted_at = Some(clock.unix_timestamp);
+
+        emit!(MarketHalted {
+            market_id: market.id,
+            reason_code,
+            authority: ctx.accounts.resolver.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} halted (reason {})", market.id, reason_code);
+        Ok(())
+    }
+
+    /// Resume trading on a halted market (RESOLVER ROLE OR ADMIN)
+    pub fn resume_market(
+        ctx: Context<SetMarketHalt>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.resolver.key(), &ctx.accounts.resolver_role),
+            MarketError::UnauthorizedResolver
+        );
+        require!(market.status == MarketStatus::Halted, MarketError::MarketNotHalted);
+
+        market.status = MarketStatus::Active;
+        market.halted_at = None;
+
+        emit!(MarketResumed {
+            market_id: market.id,
+            authority: ctx.accounts.resolver.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Market {} resumed", market.id);
+        Ok(())
+    }
+
+    /// Claim winnings from a resolved market, or a refund if it resolved Invalid
+    pub fn claim_winnings(
+        ctx: Context<ClaimWinnings>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let bet = &mut ctx.accounts.bet;
+        let user_stats = &mut ctx.accounts.user_stats;
+
+        ctx.accounts.config.require_not_paused(PauseScope::Claiming)?;
+
+        require!(market.is_finalized, MarketError::ResolutionNotFinalized);
+        require!(market.status == MarketStatus::Resolved, MarketError::MarketNotResolved);
+        require!(!market.residual_swept, MarketError::ClaimWindowClosed);
+        require!(!bet.claimed, MarketError::AlreadyClaimed);
+        require!(bet.user == ctx.accounts.user.key(), MarketError::NotBetOwner);
+
+        let outcome = market.outcome.ok_or(MarketError::MarketNotResolved)?;
+        market.bets_claimed = market.bets_claimed.checked_add(1).ok_or(MarketError::MathOverflow)?;
+
+        let late_bet = market.is_late_bet(bet.timestamp);
+        let one_sided = market.is_one_sided_scalar();
+        let total_payout = market.gross_payout(bet.amount, bet.outcome_index, outcome)?;
+
+        if outcome == Outcome::Invalid || late_bet || one_sided {
+            // Void market, bet placed after the event or one-sided scalar market:
+            // refund the stake, no fee, not a win or a loss
+            let market_id_bytes = market.id.to_le_bytes();
+            let vault_bump = &[market.vault_bump];
+            let seeds = &[
+                b"vault".as_ref(),
+                market_id_bytes.as_ref(),
+                vault_bump.as_ref(),
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, bet.amount)?;
+
+            bet.payout = bet.amount;
+            bet.claimed = true;
+
+            if late_bet {
+                msg!("Bet placed after the event, refunded {} USDC", bet.amount);
+            } else if one_sided {
+                msg!("One-sided scalar market, refunded {} USDC", bet.amount);
+            } else {
+                msg!("Market void, refunded {} USDC", bet.amount);
+            }
+        } else if total_payout > 0 {
+            // Winner (or a scalar side with a share of the pool)
+            // Deduct the market fee, split between creator and protocol
+            let fee = market.fee_on(total_payout)?;
+            let payout_after_fee = total_payout.checked_sub(fee).ok_or(MarketError::MathOverflow)?;
+            market.accrue_fee(fee)?;
+
+            // Transfer winnings from vault to user
+            let market_id_bytes = market.id.to_le_bytes();
+            let vault_bump = &[market.vault_bump];
+            let seeds = &[
+                b"vault".as_ref(),
+                market_id_bytes.as_ref(),
+                vault_bump.as_ref(),
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, payout_after_fee)?;
+
+            bet.payout = payout_after_fee;
+            bet.claimed = true;
+
+            // Update user stats: only a payout above the stake counts as a win,
+            // a scalar side paid back less than its stake counts as a loss
+            if payout_after_fee > bet.amount {
+                user_stats.total_won = user_stats.total_won.checked_add(payout_after_fee).ok_or(MarketError::MathOverflow)?;
+                user_stats.win_count = user_stats.win_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+                user_stats.current_streak = user_stats.current_streak.checked_add(1).ok_or(MarketError::MathOverflow)?;
+                if user_stats.current_streak > user_stats.best_streak {
+                    user_stats.best_streak = user_stats.current_streak;
+                }
+            } else if payout_after_fee < bet.amount {
+                let shortfall = bet.amount - payout_after_fee;
+                user_stats.total_lost = user_stats.total_lost.checked_add(shortfall).ok_or(MarketError::MathOverflow)?;
+                user_stats.loss_count = user_stats.loss_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+                user_stats.current_streak = 0;
+            }
+
+            let profit = (payout_after_fee as i64).checked_sub(bet.amount as i64).ok_or(MarketError::MathOverflow)?;
+            user_stats.net_profit = user_stats.net_profit.checked_add(profit).ok_or(MarketError::MathOverflow)?;
+
+            msg!("User received {} USDC", payout_after_fee);
+        } else {
+            // Loser - no payout
+            bet.payout = 0;
+            bet.claimed = true;
+
+            user_stats.total_lost = user_stats.total_lost.checked_add(bet.amount).ok_or(MarketError::MathOverflow)?;
+            user_stats.loss_count = user_stats.loss_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+            user_stats.current_streak = 0;
+
+            let loss = -(bet.amount as i64);
+            user_stats.net_profit = user_stats.net_profit.checked_add(loss).ok_or(MarketError::MathOverflow)?;
+
+            msg!("User lost the bet");
+        }
+
+        Ok(())
+    }
+
+    /// Cancel market (MARKET CREATOR ROLE OR ADMIN, only if no bets placed)
+    pub fn cancel_market(
+        ctx: Context<CancelMarket>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.curator.key(), &ctx.accounts.curator_role),
+            MarketError::Unauthorized
+        );
+
+        let market = &mut ctx.accounts.market;
+
+        require!(market.total_bets_count == 0, MarketError::HasBets);
+        require!(
+            market.status == MarketStatus::Active || market.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+
+        market.status = MarketStatus::Cancelled;
+        market.halted_at = None;
+        market.admin_voided = true;
+
+        msg!("Market {} cancelled", market.id);
+        Ok(())
+    }
+
+    /// Release a market's creation bond (PERMISSIONLESS)
+    /// Returned to the creator once the market is resolved, even as Invalid;
+    /// slashed to the fee recipient only if an admin cancelled or voided it
+    pub fn settle_creation_bond(
+        ctx: Context<SettleCreationBond>,
+        _market_id: u64,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let market = &mut ctx.accounts.market;
+        let destination = &ctx.accounts.destination;
+
+        config.require_not_paused(PauseScope::All)?;
+
+        let bond = market.creation_bond;
+        require!(bond > 0, MarketError::NoCreationBond);
+
+        match market.status {
+            MarketStatus::Resolved => require!(market.is_finalized, MarketError::MarketNotResolved),
+            MarketStatus::Cancelled => {}
+            _ => return err!(MarketError::MarketNotResolved),
+        }
+        let slashed = market.admin_voided;
+        let recipient = if slashed { config.fee_recipient } else { market.creator };
+        require!(destination.owner == recipient, MarketError::InvalidBondDestination);
+
+        market.creation_bond = 0;
+
+        let escrow_bump = &[ctx.bumps.bond_escrow];
+        let seeds = &[
+            b"bond_escrow".as_ref(),
+            escrow_bump.as_ref(),
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.bond_escrow.to_account_info(),
+            to: destination.to_account_info(),
+            authority: ctx.accounts.bond_escrow.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, bond)?;
+
+        if slashed {
+            msg!("Creation bond of {} for market {} slashed to fee recipient", bond, market.id);
+        } else {
+            msg!("Creation bond of {} for market {} returned to creator", bond, market.id);
+        }
+        Ok(())
+    }
+
+    /// Withdraw the protocol's share of a market's fees (TREASURER ROLE OR ADMIN)
+    pub fn withdraw_fees(
+        ctx: Context<WithdrawFees>,
+        market_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::FeeWithdrawal)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.treasurer.key(), &ctx.accounts.treasurer_role),
+            MarketError::Unauthorized
+        );
+
+        let market = &mut ctx.accounts.market;
+
+        // Market must be resolved to withdraw fees
+        require!(market.status == MarketStatus::Resolved, MarketError::MarketNotResolved);
+
+        // Only fees accrued by claims so far, never bettor collateral
+        let fees = market.protocol_fees_accrued
+            .checked_sub(market.protocol_fees_withdrawn)
+            .ok_or(MarketError::MathOverflow)?;
+
+        require!(fees > 0, MarketError::NoFeesToWithdraw);
+
+        market.protocol_fees_withdrawn = market.protocol_fees_accrued;
+
+        // Transfer fees to the configured fee recipient
+        let market_id_bytes = market_id.to_le_bytes();
+        let vault_bump = &[market.vault_bump];
+        let seeds = &[
+            b"vault".as_ref(),
+            market_id_bytes.as_ref(),
+            vault_bump.as_ref(),
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.fee_recipient_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, fees)?;
+
+        msg!("Withdrawn {} fees from market {} to fee recipient", fees, market_id);
+        Ok(())
+    }
+
+    /// Sweep what is left in a resolved market's vault to the fee recipient
+    /// (TREASURER ROLE OR ADMIN)
+    /// Allowed once every bet has been claimed or the claim window has closed;
+    /// unclaimed creator fees stay in the vault, later bet claims are refused
+    pub fn sweep_residual(
+        ctx: Context<SweepResidual>,
+        market_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::FeeWithdrawal)?;
+        require!(
+            ctx.accounts.config.has_role(&ctx.accounts.treasurer.key(), &ctx.accounts.treasurer_role),
+            MarketError::Unauthorized
+        );
+
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.status == MarketStatus::Resolved, MarketError::MarketNotResolved);
+        require!(market.is_finalized, MarketError::ResolutionNotFinalized);
+        require!(!market.residual_swept, MarketError::ClaimWindowClosed);
+
+        let resolved_at = market.resolution_time.ok_or(MarketError::MarketNotResolved)?;
+        let claims_close = resolved_at
+            .checked_add(ctx.accounts.config.claim_window)
+            .ok_or(MarketError::MathOverflow)?;
+        require!(
+            market.bets_claimed == market.total_bets_count || clock.unix_timestamp >= claims_close,
+            MarketError::ClaimWindowOpen
+        );
+
+        let creator_fees = market.creator_fees_accrued
+            .checked_sub(market.creator_fees_claimed)
+            .ok_or(MarketError::MathOverflow)?;
+        let residual = ctx.accounts.vault.amount
+            .checked_sub(creator_fees)
+            .ok_or(MarketError::MathOverflow)?;
+
+        require!(residual > 0, MarketError::NoFeesToWithdraw);
+
+        // Unwithdrawn protocol fees are part of the residual
+        market.protocol_fees_withdrawn = market.protocol_fees_accrued;
+        market.residual_swept = true;
+
+        let market_id_bytes = market_id.to_le_bytes();
+        let vault_bump = &[market.vault_bump];
+        let seeds = &[
+            b"vault".as_ref(),
+            market_id_bytes.as_ref(),
+            vault_bump.as_ref(),
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.fee_recipient_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, residual)?;
+
+        msg!("Swept {} residual from market {} to fee recipient", residual, market_id);
+        Ok(())
+    }
+
+    /// Claim the creator's share of a market's fees (MARKET CREATOR ONLY)
+    pub fn claim_creator_fees(
+        ctx: Context<ClaimCreatorFees>,
+        market_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::FeeWithdrawal)?;
+
+        let market = &mut ctx.accounts.market;
+
+        require!(ctx.accounts.creator.key() == market.creator, MarketError::NotCreator);
+
+        let fees = market.creator_fees_accrued
+            .checked_sub(market.creator_fees_claimed)
+            .ok_or(MarketError::MathOverflow)?;
+
+        require!(fees > 0, MarketError::NoFeesToWithdraw);
+
+        market.creator_fees_claimed = market.creator_fees_accrued;
+
+        let market_id_bytes = market_id.to_le_bytes();
+        let vault_bump = &[market.vault_bump];
+        let seeds = &[
+            b"vault".as_ref(),
+            market_id_bytes.as_ref(),
+            vault_bump.as_ref(),
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, fees)?;
+
+        msg!("Creator claimed {} fees from market {}", fees, market_id);
+        Ok(())
+    }
+
+    /// Create an M-of-N multisig
+    /// Its authority PDA can be made admin (or market creator) so privileged
+    /// instructions only run after `threshold` signers approve them
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        multisig_id: u64,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+            MarketError::InvalidMultisigSigners
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), MarketError::InvalidMultisigSigners);
+        }
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            MarketError::InvalidThreshold
+        );
+
+        multisig.id = multisig_id;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.action_count = 0;
+        multisig.authority_bump = ctx.bumps.authority;
+        multisig.bump = ctx.bumps.multisig;
+
+        msg!("Multisig {} created ({} of {})",
+            multisig_id, threshold, multisig.signers.len());
+        Ok(())
+    }
+
+    /// Propose an instruction to be executed by the multisig authority (SIGNERS ONLY)
+    /// The proposer's approval is recorded automatically
+    pub fn propose_action(
+        ctx: Context<ProposeAction>,
+        program_id: Pubkey,
+        accounts: Vec<ActionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let action = &mut ctx.accounts.action;
+        let clock = Clock::get()?;
+
+        let signer_index = multisig.signer_index(&ctx.accounts.proposer.key())?;
+        require!(accounts.len() <= MAX_ACTION_ACCOUNTS, MarketError::ActionTooLarge);
+        require!(data.len() <= MAX_ACTION_DATA_LEN, MarketError::ActionTooLarge);
+
+        action.multisig = multisig.key();
+        action.index = multisig.action_count;
+        action.proposer = ctx.accounts.proposer.key();
+        action.program_id = program_id;
+        action.accounts = accounts;
+        action.data = data;
+        action.approvals = vec![false; multisig.signers.len()];
+        action.approvals[signer_index] = true;
+        action.executed = false;
+        action.created_at = clock.unix_timestamp;
+
+        multisig.action_count = multisig.action_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+
+        msg!("Multisig {} action {} proposed", multisig.id, action.index);
+        Ok(())
+    }
+
+    /// Approve a pending multisig action (SIGNERS ONLY)
+    pub fn approve_action(
+        ctx: Context<ApproveAction>,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let action = &mut ctx.accounts.action;
+
+        require!(!action.executed, MarketError::ActionAlreadyExecuted);
+        let signer_index = multisig.signer_index(&ctx.accounts.approver.key())?;
+
+        action.approvals[signer_index] = true;
+
+        msg!("Multisig {} action {} approved by {}",
+            multisig.id, action.index, ctx.accounts.approver.key());
+        Ok(())
+    }
+
+    /// Execute a multisig action once enough signers have approved it
+    /// Every account referenced by the action (and the target program) must be
+    /// passed as remaining accounts
+    pub fn execute_action(
+        ctx: Context<ExecuteAction>,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let action = &mut ctx.accounts.action;
+
+        require!(!action.executed, MarketError::ActionAlreadyExecuted);
+        let approvals = action.approvals.iter().filter(|approved| **approved).count();
+        require!(approvals >= multisig.threshold as usize, MarketError::NotEnoughApprovals);
+
+        // Mark executed and persist it before the CPI, so a nested call back into
+        // this instruction already sees the action as executed
+        action.executed = true;
+        action.exit(&crate::ID)?;
+
+        let authority = ctx.accounts.authority.key();
+        let ix = Instruction {
+            program_id: action.program_id,
+            accounts: action.accounts.iter().map(|acc| AccountMeta {
+                pubkey: acc.pubkey,
+                is_signer: acc.is_signer || acc.pubkey == authority,
+                is_writable: acc.is_writable,
+            }).collect(),
+            data: action.data.clone(),
+        };
+
+        let multisig_key = multisig.key();
+        let authority_bump = &[multisig.authority_bump];
+        let seeds = &[
+            b"multisig_authority".as_ref(),
+            multisig_key.as_ref(),
+            authority_bump.as_ref(),
+        ];
+        let signer = &[&seeds[..]];
+
+        invoke_signed(&ix, ctx.remaining_accounts, signer)?;
+
+        msg!("Multisig {} action {} executed", multisig.id, action.index);
+        Ok(())
+    }
+}
+
+// ========== ACCOUNTS ==========
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub collateral_mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ MarketError::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::PredictionMarkets>,
+
+    // Only the upgrade authority can bootstrap the config
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ MarketError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, wallet: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RoleGrant::INIT_SPACE,
+        seeds = [b"role".as_ref(), &[role as u8], wallet.as_ref()],
+        bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, wallet: Pubkey)]
+pub struct RevokeRole<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"role".as_ref(), &[role as u8], wallet.as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Guardian as u8], authority.key().as_ref()],
+        bump = guardian_role.bump
+    )]
+    pub guardian_role: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CreateMarket<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: USDC mint address, must match the configured collateral mint
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: AccountInfo<'info>,
+
+    // Shared escrow holding creation bonds of every market
+    #[account(
+        init_if_needed,
+        payer = creator,
+        token::mint = usdc_mint,
+        token::authority = bond_escrow,
+        seeds = [b"bond_escrow"],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // Required when the creator has to post a bond
+    #[account(mut)]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MarketCreator as u8], creator.key().as_ref()],
+        bump = creator_role.bump
+    )]
+    pub creator_role: Option<Account<'info, RoleGrant>>,
+
+    // Required for conditional markets
+    pub parent_market: Option<Account<'info, Market>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ProposeMarket<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MarketProposal::INIT_SPACE,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, MarketProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, market_id: u64)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, MarketProposal>,
+
+    #[account(
+        init,
+        payer = curator,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = curator,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: USDC mint address, must match the configured collateral mint
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MarketCreator as u8], curator.key().as_ref()],
+        bump = curator_role.bump
+    )]
+    pub curator_role: Option<Account<'info, RoleGrant>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct RejectProposal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, MarketProposal>,
+
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MarketCreator as u8], curator.key().as_ref()],
+        bump = curator_role.bump
+    )]
+    pub curator_role: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct PlaceBet<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Bet::INIT_SPACE,
+        seeds = [b"bet", market.key().as_ref(), user.key().as_ref(), &market.total_bets_count.to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResolveMarket<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub resolver: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Resolver as u8], resolver.key().as_ref()],
+        bump = resolver_role.bump
+    )]
+    pub resolver_role: Option<Account<'info, RoleGrant>>,
+
+    // Required for conditional markets
+    pub parent_market: Option<Account<'info, Market>>,
+
+    // Early resolution: the last bet placed before the event (none if there is
+    // no such bet) and the first bet placed after it (none if there is no such bet)
+    pub checkpoint_bet: Option<Account<'info, Bet>>,
+    pub next_bet: Option<Account<'info, Bet>>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResolveWithOracle<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Must be the market's configured feed; owner and layout are checked when decoding
+    pub price_feed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SubmitAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    // One attestation per committee member
+    #[account(
+        init,
+        payer = resolver,
+        space = 8 + Attestation::INIT_SPACE,
+        seeds = [b"attestation", market_id.to_le_bytes().as_ref(), resolver.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    /// CHECK: USDC mint address, only needed to open a dispute
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: Option<AccountInfo<'info>>,
+
+    // Required when this attestation conflicts with the leading outcome
+    #[account(
+        init,
+        payer = resolver,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+
+    #[account(
+        init,
+        payer = resolver,
+        token::mint = usdc_mint,
+        token::authority = dispute_vault,
+        seeds = [b"dispute_vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dispute_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ProposeResolution<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: USDC mint address, must match the configured collateral mint
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        token::mint = usdc_mint,
+        token::authority = bond_escrow,
+        seeds = [b"bond_escrow"],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    // Required for conditional markets
+    pub parent_market: Option<Account<'info, Market>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ChallengeResolution<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: USDC mint address, must match the configured collateral mint
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = challenger,
+        token::mint = usdc_mint,
+        token::authority = bond_escrow,
+        seeds = [b"bond_escrow"],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        init,
+        payer = challenger,
+        token::mint = usdc_mint,
+        token::authority = dispute_vault,
+        seeds = [b"dispute_vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dispute_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct FinalizeResolution<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_escrow"],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    // Required when the proposer posted a bond
+    #[account(mut)]
+    pub proposer_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct VoidExpiredMarket<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct VoidMarket<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CommitVote<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market_id.to_le_bytes().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute_vault", market_id.to_le_bytes().as_ref()],
+        bump = dispute.vault_bump
+    )]
+    pub dispute_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = juror,
+        space = 8 + JurorVote::INIT_SPACE,
+        seeds = [b"juror_vote", market_id.to_le_bytes().as_ref(), juror.key().as_ref()],
+        bump
+    )]
+    pub juror_vote: Account<'info, JurorVote>,
+
+    #[account(mut)]
+    pub juror_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub juror: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct RevealVote<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market_id.to_le_bytes().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [b"juror_vote", market_id.to_le_bytes().as_ref(), juror.key().as_ref()],
+        bump = juror_vote.bump
+    )]
+    pub juror_vote: Account<'info, JurorVote>,
+
+    pub juror: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SettleDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market_id.to_le_bytes().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute_vault", market_id.to_le_bytes().as_ref()],
+        bump = dispute.vault_bump
+    )]
+    pub dispute_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_escrow"],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    // Token account of whichever side the jurors agreed with
+    #[account(mut)]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimJurorReward<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"dispute", market_id.to_le_bytes().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute_vault", market_id.to_le_bytes().as_ref()],
+        bump = dispute.vault_bump
+    )]
+    pub dispute_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"juror_vote", market_id.to_le_bytes().as_ref(), juror.key().as_ref()],
+        bump = juror_vote.bump
+    )]
+    pub juror_vote: Account<'info, JurorVote>,
+
+    #[account(mut)]
+    pub juror_token_account: Account<'info, TokenAccount>,
+
+    pub juror: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SubmitReport<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: USDC mint address, must match the configured collateral mint
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = 8 + Reporting::INIT_SPACE,
+        seeds = [b"reporting", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reporting: Account<'info, Reporting>,
+
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        token::mint = usdc_mint,
+        token::authority = reporting_vault,
+        seeds = [b"reporting_vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reporting_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + Report::INIT_SPACE,
+        seeds = [b"report", market_id.to_le_bytes().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, Report>,
+
+    #[account(mut)]
+    pub reporter_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SettleReports<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Reporting::INIT_SPACE,
+        seeds = [b"reporting", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reporting: Account<'info, Reporting>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimReport<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"reporting", market_id.to_le_bytes().as_ref()],
+        bump = reporting.bump
+    )]
+    pub reporting: Account<'info, Reporting>,
+
+    #[account(
+        mut,
+        seeds = [b"reporting_vault", market_id.to_le_bytes().as_ref()],
+        bump = reporting.vault_bump
+    )]
+    pub reporting_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"report", market_id.to_le_bytes().as_ref(), reporter.key().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, Report>,
+
+    #[account(mut)]
+    pub reporter_token_account: Account<'info, TokenAccount>,
+
+    pub reporter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureParlayHouse<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ParlayHouse::INIT_SPACE,
+        seeds = [b"parlay_house"],
+        bump
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+
+    /// CHECK: USDC mint address, must match the configured collateral mint
+    #[account(address = config.collateral_mint @ MarketError::InvalidCollateralMint)]
+    pub usdc_mint: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = usdc_mint,
+        token::authority = parlay_vault,
+        seeds = [b"parlay_vault"],
+        bump
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundParlayHouse<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"parlay_house"],
+        bump = parlay_house.bump
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump = parlay_house.vault_bump
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawParlayHouse<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"parlay_house"],
+        bump = parlay_house.bump
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump = parlay_house.vault_bump
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_token_account.owner == config.fee_recipient @ MarketError::InvalidFeeRecipient
+    )]
+    pub fee_recipient_token_account: Account<'info, TokenAccount>,
+
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Treasurer as u8], treasurer.key().as_ref()],
+        bump = treasurer_role.bump
+    )]
+    pub treasurer_role: Option<Account<'info, RoleGrant>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(parlay_id: u64)]
+pub struct PlaceParlay<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_house"],
+        bump = parlay_house.bump
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump = parlay_house.vault_bump
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Parlay::INIT_SPACE,
+        seeds = [b"parlay", user.key().as_ref(), parlay_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(parlay_id: u64)]
+pub struct SettleParlay<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_house"],
+        bump = parlay_house.bump
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump = parlay_house.vault_bump
+    )]
+    pub parlay_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay", parlay.user.as_ref(), parlay_id.to_le_bytes().as_ref()],
+        bump = parlay.bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == parlay.user @ MarketError::NotBetOwner
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SetMarketHalt<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub resolver: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Resolver as u8], resolver.key().as_ref()],
+        bump = resolver_role.bump
+    )]
+    pub resolver_role: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimWinnings<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = bet.market == market.key() @ MarketError::BetMarketMismatch
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MarketCreator as u8], curator.key().as_ref()],
+        bump = curator_role.bump
+    )]
+    pub curator_role: Option<Account<'info, RoleGrant>>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SettleCreationBond<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_escrow"],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    // Creator's token account on return, fee recipient's on slash
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_token_account.owner == config.fee_recipient @ MarketError::InvalidFeeRecipient
+    )]
+    pub fee_recipient_token_account: Account<'info, TokenAccount>,
+
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Treasurer as u8], treasurer.key().as_ref()],
+        bump = treasurer_role.bump
+    )]
+    pub treasurer_role: Option<Account<'info, RoleGrant>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SweepResidual<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_token_account.owner == config.fee_recipient @ MarketError::InvalidFeeRecipient
+    )]
+    pub fee_recipient_token_account: Account<'info, TokenAccount>,
+
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Treasurer as u8], treasurer.key().as_ref()],
+        bump = treasurer_role.bump
+    )]
+    pub treasurer_role: Option<Account<'info, RoleGrant>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(multisig_id: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [b"multisig", multisig_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: PDA that signs executed actions, holds no data
+    #[account(
+        seeds = [b"multisig_authority", multisig.key().as_ref()],
+        bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MultisigAction::INIT_SPACE,
+        seeds = [b"multisig_action", multisig.key().as_ref(), &multisig.action_count.to_le_bytes()],
+        bump
+    )]
+    pub action: Account<'info, MultisigAction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAction<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig)]
+    pub action: Account<'info, MultisigAction>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig)]
+    pub action: Account<'info, MultisigAction>,
+
+    /// CHECK: PDA that signs the executed instruction
+    #[account(
+        seeds = [b"multisig_authority", multisig.key().as_ref()],
+        bump = multisig.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// ========== DATA STRUCTURES ==========
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // Set by propose_admin, cleared on accept/cancel
+    pub fee_recipient: Pubkey,    // Owner of the token account that receives withdrawn fees
+    pub collateral_mint: Pubkey,  // Mint accepted for bets (USDC)
+    pub default_fee_bps: u16,     // Fee used when a creator does not pick one
+    pub max_fee_bps: u16,         // Upper bound on any market fee
+    pub creator_fee_share_bps: u16, // Portion of each market's fees paid to its creator
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub creation_bond: u64,       // Bond posted by creators without the market creator role
+    pub resolution_bond: u64,     // Bond posted with propose_resolution and matched by challengers
+    pub challenge_period: i64,    // Seconds a proposed resolution can be challenged
+    pub min_juror_stake: u64,     // Smallest stake accepted by commit_vote
+    pub commit_period: i64,       // Seconds jurors have to commit after a challenge
+    pub reveal_period: i64,       // Seconds jurors have to reveal after the commit period
+    pub resolution_window: i64,   // Seconds after end_time a market has to be resolved
+    pub claim_window: i64,        // Seconds after resolution bets can be claimed before the vault is swept
+    pub bump: u8,
+
+    // Circuit breaker
+    pub paused: bool,             // Stops every market instruction
+    pub betting_paused: bool,
+    pub claiming_paused: bool,
+    pub creation_paused: bool,
+    pub withdrawals_paused: bool,
+}
+
+impl Config {
+    pub fn apply(&mut self, params: ConfigParams) -> Result<()> {
+        require!(params.max_fee_bps as u64 <= BPS_DENOMINATOR, MarketError::InvalidFee);
+        require!(params.default_fee_bps <= params.max_fee_bps, MarketError::InvalidFee);
+        require!(params.creator_fee_share_bps as u64 <= BPS_DENOMINATOR, MarketError::InvalidFee);
+        require!(
+            params.min_bet > 0 && params.min_bet <= params.max_bet,
+            MarketError::InvalidBetLimits
+        );
+        require!(params.challenge_period > 0, MarketError::InvalidChallengePeriod);
+        require!(
+            params.commit_period > 0
+                && params.reveal_period > 0
+                && params.resolution_window > 0
+                && params.claim_window > 0,
+            MarketError::InvalidChallengePeriod
+        );
+
+        self.fee_recipient = params.fee_recipient;
+        self.default_fee_bps = params.default_fee_bps;
+        self.max_fee_bps = params.max_fee_bps;
+        self.creator_fee_share_bps = params.creator_fee_share_bps;
+        self.min_bet = params.min_bet;
+        self.max_bet = params.max_bet;
+        self.creation_bond = params.creation_bond;
+        self.resolution_bond = params.resolution_bond;
+        self.challenge_period = params.challenge_period;
+        self.min_juror_stake = params.min_juror_stake;
+        self.commit_period = params.commit_period;
+        self.reveal_period = params.reveal_period;
+        self.resolution_window = params.resolution_window;
+        self.claim_window = params.claim_window;
+        Ok(())
+    }
+}
+
+impl Config {
+    /// The admin implicitly holds every role; anyone else needs a RoleGrant,
+    /// whose seeds (role + wallet) are already checked by the accounts struct
+    pub fn has_role(&self, wallet: &Pubkey, grant: &Option<Account<RoleGrant>>) -> bool {
+        *wallet == self.admin || grant.is_some()
+    }
+
+    /// Fee for a new market: the creator's choice bounded by max_fee_bps, or the default
+    pub fn market_fee_bps(&self, fee_bps: Option<u16>) -> Result<u16> {
+        match fee_bps {
+            Some(fee_bps) => {
+                require!(fee_bps <= self.max_fee_bps, MarketError::InvalidFee);
+                Ok(fee_bps)
+            }
+            None => Ok(self.default_fee_bps),
+        }
+    }
+
+    pub fn set_paused(&mut self, scope: PauseScope, paused: bool) {
+        match scope {
+            PauseScope::All => self.paused = paused,
+            PauseScope::Betting => self.betting_paused = paused,
+            PauseScope::Claiming => self.claiming_paused = paused,
+            PauseScope::Creation => self.creation_paused = paused,
+            PauseScope::FeeWithdrawal => self.withdrawals_paused = paused,
+        }
+    }
+
+    /// Fails if the protocol is globally paused or `scope` is paused
+    pub fn require_not_paused(&self, scope: PauseScope) -> Result<()> {
+        let scope_paused = match scope {
+            PauseScope::All => false,
+            PauseScope::Betting => self.betting_paused,
+            PauseScope::Claiming => self.claiming_paused,
+            PauseScope::Creation => self.creation_paused,
+            PauseScope::FeeWithdrawal => self.withdrawals_paused,
+        };
+        require!(!self.paused && !scope_paused, MarketError::ProtocolPaused);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub fee_recipient: Pubkey,
+    pub default_fee_bps: u16,
+    pub max_fee_bps: u16,
+    pub creator_fee_share_bps: u16,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub creation_bond: u64,
+    pub resolution_bond: u64,
+    pub challenge_period: i64,
+    pub min_juror_stake: u64,
+    pub commit_period: i64,
+    pub reveal_period: i64,
+    pub resolution_window: i64,
+    pub claim_window: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    pub id: u64,
+    #[max_len(200)]
+    pub question: String,
+    #[max_len(500)]
+    pub description: String,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub end_time: i64,
+    pub resolution_time: Option<i64>,
+    pub outcome: Option<Outcome>, // None = unresolved, proposed outcome until finalized
+    #[max_len(50)]
+    pub category: String,
+    #[max_len(200)]
+    pub resolution_source: String, // Declared at creation: where the outcome is read from
+    pub market_type: MarketType,
+
+    // Categorical and bucket pools, one per outcome; scalar LONG and SHORT pools (empty for binary markets)
+    #[max_len(MAX_OUTCOMES)]
+    pub outcome_pools: Vec<u64>,
+
+    // AMM pools
+    pub yes_pool: u64,  // Virtual YES tokens
+    pub no_pool: u64,   // Virtual NO tokens
+
+    // Stats
+    pub total_yes_bets: u64,
+    pub total_no_bets: u64,
+    pub total_volume: u64,
+    pub total_bets_count: u64,
+    pub unique_bettors: u64,
+
+    pub fee_bps: u16,   // Fee taken from winning payouts, in basis points
+    pub status: MarketStatus,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+
+    // Decentralized resolution
+    pub resolution_proposer: Option<Pubkey>,  // Who proposed the current resolution
+    pub resolution_bond: u64,                  // Current bond amount staked
+    pub challenge_deadline: Option<i64>,       // When challenge period ends
+    pub is_finalized: bool,                    // True after challenge period with no disputes
+    pub resolver: Pubkey,                      // Wallet that resolved the market
+    pub challenger: Option<Pubkey>,            // Who disputed the proposed outcome
+    pub challenge_bond: u64,                   // Counter-bond held while disputed
+    pub resolution_mode: ResolutionMode,       // How the outcome gets decided
+    pub attestation_count: u8,                 // Committee attestations agreeing with `outcome`
+    pub resolution_deadline: i64,              // After this an unresolved market can be voided
+    pub evidence: ResolutionEvidence,          // Supplied by the proposer of the current outcome
+    pub event_occurred_at: Option<i64>,        // Set on early resolution; later bets are refunded
+    pub parent_market: Option<Pubkey>,         // Conditional markets only resolve if the parent
+    pub required_outcome: Option<Outcome>,     // resolves to this outcome, otherwise they are voided
+
+    // Trading halt
+    pub halt_reason: u16,                      // Reason code of the latest halt
+    pub halted_at: Option<i64>,                // Set while the market is halted
+
+    pub creation_bond: u64,                    // Bond held in escrow until settle_creation_bond
+    pub admin_voided: bool,                    // Cancelled or voided by an admin, slashes the bond
+
+    // Fee accounting (all amounts stay in the vault until claimed)
+    pub creator_fee_share_bps: u16,            // Snapshot of the config split at creation
+    pub creator_fees_accrued: u64,
+    pub creator_fees_claimed: u64,
+    pub protocol_fees_accrued: u64,
+    pub protocol_fees_withdrawn: u64,
+
+    // Residual collateral (rounding, virtual pool surplus, pools nobody won)
+    pub bets_claimed: u64,                     // Bets settled by claim_winnings, winners or not
+    pub residual_swept: bool,                  // Set by sweep_residual, which closes claims
+}
+
+impl Market {
+    /// Writes a freshly created market; vault fields are set by the caller
+    pub fn initialize(
+        &mut self,
+        id: u64,
+        creator: Pubkey,
+        details: MarketDetails,
+        fee_bps: u16,
+        config: &Config,
+        now: i64,
+    ) -> Result<()> {
+        self.id = id;
+        self.question = details.question;
+        self.description = details.description;
+        self.creator = creator;
+        self.created_at = now;
+        self.end_time = details.end_time;
+        self.resolution_time = None;
+        self.outcome = None;
+        self.category = details.category;
+        self.resolution_source = details.resolution_source;
+        self.outcome_pools = match &details.market_type {
+            MarketType::Binary => Vec::new(),
+            MarketType::Categorical(outcomes) => vec![0; outcomes.labels.len()],
+            MarketType::Buckets(buckets) => vec![0; buckets.count()],
+            MarketType::Scalar(_) => vec![0; 2],
+        };
+        self.market_type = details.market_type;
+
+        // Initialize AMM pools with 50/50 ratio (1000 USDC each side for virtual liquidity)
+        self.yes_pool = VIRTUAL_POOL;
+        self.no_pool = VIRTUAL_POOL;
+        self.total_yes_bets = 0;
+        self.total_no_bets = 0;
+        self.total_volume = 0;
+        self.total_bets_count = 0;
+        self.unique_bettors = 0;
+
+        self.fee_bps = fee_bps;
+        self.status = MarketStatus::Active;
+        self.resolver = Pubkey::default(); // Set when the market is resolved
+
+        // Decentralized resolution fields
+        self.resolution_proposer = None;
+        self.resolution_bond = 0;
+        self.challenge_deadline = None;
+        self.is_finalized = false;
+        self.challenger = None;
+        self.challenge_bond = 0;
+        self.resolution_mode = details.resolution_mode;
+        self.attestation_count = 0;
+        self.resolution_deadline = self.end_time
+            .checked_add(config.resolution_window)
+            .ok_or(MarketError::MathOverflow)?;
+        self.evidence = ResolutionEvidence::default();
+        self.event_occurred_at = None;
+        self.parent_market = details.parent_market;
+        self.required_outcome = details.required_outcome;
+
+        self.halt_reason = 0;
+        self.halted_at = None;
+
+        self.creation_bond = 0;
+        self.admin_voided = false;
+
+        self.creator_fee_share_bps = config.creator_fee_share_bps;
+        self.creator_fees_accrued = 0;
+        self.creator_fees_claimed = 0;
+        self.protocol_fees_accrued = 0;
+        self.protocol_fees_withdrawn = 0;
+        self.bets_claimed = 0;
+        self.residual_swept = false;
+        Ok(())
+    }
+
+    /// Records a proposed outcome and opens the challenge window
+    /// (a halted market can still be resolved once it has ended)
+    pub fn propose_resolution(
+        &mut self,
+        proposer: Pubkey,
+        outcome: Outcome,
+        evidence: ResolutionEvidence,
+        bond: u64,
+        challenge_period: i64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            self.status == MarketStatus::Active || self.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        require!(
+            now >= self.end_time || self.event_occurred_at.is_some(),
+            MarketError::MarketNotEnded
+        );
+        require!(self.resolution_mode == ResolutionMode::Manual, MarketError::WrongResolutionMode);
+        self.require_valid_outcome(outcome)?;
+        evidence.validate()?;
+
+        self.outcome = Some(outcome);
+        self.evidence = evidence;
+        self.status = MarketStatus::ResolutionProposed;
+        self.resolution_proposer = Some(proposer);
+        self.resolution_bond = bond;
+        self.challenge_deadline = Some(now.checked_add(challenge_period).ok_or(MarketError::MathOverflow)?);
+        self.resolver = proposer;
+        self.halted_at = None;
+        Ok(())
+    }
+
+    pub fn outcome_count(&self) -> u8 {
+        match &self.market_type {
+            MarketType::Binary | MarketType::Scalar(_) => 2,
+            MarketType::Categorical(outcomes) => outcomes.labels.len() as u8,
+            MarketType::Buckets(buckets) => buckets.count() as u8,
+        }
+    }
+
+    /// Invalid fits every market; YES/NO only binary ones, categories only categorical
+    /// ones and values only scalar and bucket ones
+    pub fn require_valid_outcome(&self, outcome: Outcome) -> Result<()> {
+        let valid = match (&self.market_type, outcome) {
+            (_, Outcome::Invalid) => true,
+            (MarketType::Scalar(_), Outcome::Scalar(_)) => true,
+            _ => self.winning_index(outcome).is_some(),
+        };
+        require!(valid, MarketError::InvalidOutcome);
+        Ok(())
+    }
+
+    /// Bet outcome index that `outcome` pays out in full (scalar markets pay both sides)
+    pub fn winning_index(&self, outcome: Outcome) -> Option<u8> {
+        match (&self.market_type, outcome) {
+            (MarketType::Binary, Outcome::Yes) => Some(0),
+            (MarketType::Binary, Outcome::No) => Some(1),
+            (MarketType::Categorical(_), Outcome::Category(index)) if index < self.outcome_count() => Some(index),
+            (MarketType::Buckets(buckets), Outcome::Scalar(value)) => Some(buckets.bucket_of(value)),
+            _ => None,
+        }
+    }
+
+    pub fn add_to_pool(&mut self, outcome_index: u8, amount: u64) -> Result<()> {
+        match self.market_type {
+            MarketType::Binary if outcome_index == 0 => {
+                self.yes_pool = self.yes_pool.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+                self.total_yes_bets = self.total_yes_bets.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+            }
+            MarketType::Binary => {
+                self.no_pool = self.no_pool.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+                self.total_no_bets = self.total_no_bets.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+            }
+            MarketType::Categorical(_) | MarketType::Buckets(_) | MarketType::Scalar(_) => {
+                let pool = self.outcome_pools
+                    .get_mut(outcome_index as usize)
+                    .ok_or(MarketError::InvalidOutcome)?;
+                *pool = pool.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn total_pool(&self) -> Result<u64> {
+        self.outcome_pools
+            .iter()
+            .try_fold(0u64, |total, pool| total.checked_add(*pool))
+            .ok_or(error!(MarketError::MathOverflow))
+    }
+
+    /// A bet's payout before fees, 0 when it lost
+    pub fn gross_payout(&self, amount: u64, outcome_index: u8, outcome: Outcome) -> Result<u64> {
+        if let (MarketType::Scalar(range), Outcome::Scalar(value)) = (&self.market_type, outcome) {
+            return self.scalar_payout(range, value, amount, outcome_index);
+        }
+        if self.winning_index(outcome) != Some(outcome_index) {
+            return Ok(0);
+        }
+
+        match self.market_type {
+            MarketType::Binary => {
+                let yes_won = outcome_index == 0;
+                let winning_pool = if yes_won { self.yes_pool } else { self.no_pool };
+                let losing_pool = if yes_won { self.no_pool } else { self.yes_pool };
+
+                // Payout = (bet_amount / winning_pool) * losing_pool
+                let payout_ratio = (amount as u128)
+                    .checked_mul(losing_pool as u128)
+                    .ok_or(MarketError::MathOverflow)?
+                    .checked_div(winning_pool as u128)
+                    .ok_or(MarketError::MathOverflow)? as u64;
+
+                // Add original bet back
+                Ok(amount.checked_add(payout_ratio).ok_or(MarketError::MathOverflow)?)
+            }
+            MarketType::Categorical(_) | MarketType::Buckets(_) | MarketType::Scalar(_) => {
+                // Winners split every pool in proportion to their stake
+                let total_pool = self.total_pool()?;
+                let winning_pool = *self.outcome_pools
+                    .get(outcome_index as usize)
+                    .ok_or(MarketError::InvalidOutcome)?;
+                let payout = (amount as u128)
+                    .checked_mul(total_pool as u128)
+                    .ok_or(MarketError::MathOverflow)?
+                    .checked_div(winning_pool as u128)
+                    .ok_or(MarketError::MathOverflow)?;
+                Ok(payout as u64)
+            }
+        }
+    }
+
+    /// LONG holders share the pool's fraction at which `value` sits in the range,
+    /// SHORT holders the rest, each pro-rata to their stake
+    fn scalar_payout(&self, range: &ScalarRange, value: i64, amount: u64, outcome_index: u8) -> Result<u64> {
+        let long_pool = self.outcome_pools[0];
+        let short_pool = self.outcome_pools[1];
+
+        // One-sided market: refund the stake
+        if self.is_one_sided_scalar() {
+            return Ok(amount);
+        }
+
+        let width = (range.upper as i128) - (range.lower as i128);
+        let long_part = (value.clamp(range.lower, range.upper) as i128) - (range.lower as i128);
+        let (side_part, side_pool) = if outcome_index == 0 {
+            (long_part, long_pool)
+        } else {
+            (width - long_part, short_pool)
+        };
+
+        let side_total = (self.total_pool()? as u128)
+            .checked_mul(side_part as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(width as u128)
+            .ok_or(MarketError::MathOverflow)?;
+        let payout = side_total
+            .checked_mul(amount as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(side_pool as u128)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(payout as u64)
+    }
+
+    /// Records when the outcome became public, allowing resolution before end_time
+    pub fn record_event_time(&mut self, occurred_at: i64, now: i64) -> Result<()> {
+        require!(
+            occurred_at >= self.created_at && occurred_at <= now,
+            MarketError::InvalidEventTime
+        );
+        self.event_occurred_at = Some(occurred_at);
+        Ok(())
+    }
+
+    /// Rolls the pools back to right after `checkpoint`, the last bet placed by
+    /// `occurred_at`, so late stakes are refunded instead of being paid out or
+    /// diluting on-time winners. `next` proves no later bet was placed in time
+    pub fn checkpoint_pools(
+        &mut self,
+        market_key: Pubkey,
+        occurred_at: i64,
+        checkpoint: Option<&Bet>,
+        next: Option<&Bet>,
+    ) -> Result<()> {
+        let next_index = match checkpoint {
+            Some(bet) => {
+                require!(
+                    bet.market == market_key && bet.timestamp <= occurred_at,
+                    MarketError::InvalidBetCheckpoint
+                );
+                bet.index.checked_add(1).ok_or(MarketError::MathOverflow)?
+            }
+            None => 0,
+        };
+        if next_index < self.total_bets_count {
+            let next = next.ok_or(MarketError::InvalidBetCheckpoint)?;
+            require!(
+                next.market == market_key && next.index == next_index && next.timestamp > occurred_at,
+                MarketError::InvalidBetCheckpoint
+            );
+        }
+
+        match checkpoint {
+            Some(bet) => {
+                self.yes_pool = bet.yes_pool_after;
+                self.no_pool = bet.no_pool_after;
+                self.outcome_pools = bet.outcome_pools_after.clone();
+            }
+            None => {
+                self.yes_pool = VIRTUAL_POOL;
+                self.no_pool = VIRTUAL_POOL;
+                self.outcome_pools.iter_mut().for_each(|pool| *pool = 0);
+            }
+        }
+        Ok(())
+    }
+
+    /// A scalar market with an empty LONG or SHORT pool has nobody to pay or be paid by
+    pub fn is_one_sided_scalar(&self) -> bool {
+        matches!(self.market_type, MarketType::Scalar(_))
+            && (self.outcome_pools[0] == 0 || self.outcome_pools[1] == 0)
+    }
+
+    /// Bets placed once the outcome was already known only get their stake back
+    pub fn is_late_bet(&self, bet_timestamp: i64) -> bool {
+        self.event_occurred_at
+            .is_some_and(|occurred_at| bet_timestamp > occurred_at)
+    }
+
+    /// True unless this is a conditional market whose parent missed the required
+    /// outcome; errors while the parent is still unresolved
+    pub fn parent_condition_met(&self, parent: Option<&Account<Market>>) -> Result<bool> {
+        let (parent_key, required) = match (self.parent_market, self.required_outcome) {
+            (Some(parent_key), Some(required)) => (parent_key, required),
+            _ => return Ok(true),
+        };
+        let parent = parent.ok_or(MarketError::MissingParentMarket)?;
+        require_keys_eq!(parent.key(), parent_key, MarketError::InvalidParentMarket);
+        require!(parent.is_finalized, MarketError::ParentNotResolved);
+        Ok(parent.outcome == Some(required))
+    }
+
+    /// Reads a market passed outside the typed accounts (parlay legs)
+    pub fn load(info: &AccountInfo) -> Result<Market> {
+        require_keys_eq!(*info.owner, crate::ID, MarketError::InvalidParlayLegs);
+        let data = info.try_borrow_data()?;
+        Market::try_deserialize(&mut &data[..])
+    }
+
+    /// Decimal odds in basis points a bet on `outcome_index` would get from the
+    /// current pools, stake included
+    pub fn odds_bps(&self, outcome_index: u8) -> Result<u64> {
+        require!(outcome_index < self.outcome_count(), MarketError::InvalidOutcome);
+        let (selected, total) = match self.market_type {
+            MarketType::Binary => {
+                let selected = if outcome_index == 0 { self.yes_pool } else { self.no_pool };
+                let total = self.yes_pool.checked_add(self.no_pool).ok_or(MarketError::MathOverflow)?;
+                (selected, total)
+            }
+            MarketType::Categorical(_) | MarketType::Buckets(_) => {
+                (self.outcome_pools[outcome_index as usize], self.total_pool()?)
+            }
+            MarketType::Scalar(_) => return err!(MarketError::UnsupportedMarketType),
+        };
+        require!(selected > 0, MarketError::NoParlayOdds);
+
+        let odds = (total as u128)
+            .checked_mul(BPS_DENOMINATOR as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(selected as u128)
+            .ok_or(MarketError::MathOverflow)?;
+        u64::try_from(odds).map_err(|_| error!(MarketError::MathOverflow))
+    }
+
+    /// How a parlay leg on `outcome_index`, placed at `placed_at`, stands
+    pub fn leg_result(&self, outcome_index: u8, placed_at: i64) -> LegResult {
+        if self.status == MarketStatus::Cancelled || self.is_late_bet(placed_at) {
+            return LegResult::Void;
+        }
+        match self.outcome {
+            _ if !self.is_finalized => LegResult::Open,
+            Some(Outcome::Invalid) => LegResult::Void,
+            Some(outcome) if self.winning_index(outcome) == Some(outcome_index) => LegResult::Won,
+            _ => LegResult::Lost,
+        }
+    }
+
+    /// Resolves as Invalid straight away so every bet is refunded
+    pub fn void(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.status == MarketStatus::Active || self.status == MarketStatus::Halted,
+            MarketError::MarketNotActive
+        );
+        self.outcome = Some(Outcome::Invalid);
+        self.halted_at = None;
+        self.finalize(now);
+        Ok(())
+    }
+
+    /// Locks in `outcome`; winnings become claimable
+    pub fn finalize(&mut self, now: i64) {
+        self.status = MarketStatus::Resolved;
+        self.resolution_time = Some(now);
+        self.is_finalized = true;
+    }
+
+    pub fn fee_on(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(fee as u64)
+    }
+
+    /// Books a collected fee to the creator and protocol balances
+    pub fn accrue_fee(&mut self, fee: u64) -> Result<()> {
+        let creator_fee = (fee as u128)
+            .checked_mul(self.creator_fee_share_bps as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(MarketError::MathOverflow)? as u64;
+        let protocol_fee = fee.checked_sub(creator_fee).ok_or(MarketError::MathOverflow)?;
+
+        self.creator_fees_accrued = self.creator_fees_accrued.checked_add(creator_fee).ok_or(MarketError::MathOverflow)?;
+        self.protocol_fees_accrued = self.protocol_fees_accrued.checked_add(protocol_fee).ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// User-supplied fields shared by create_market and propose_market
+pub struct MarketDetails {
+    pub question: String,
+    pub description: String,
+    pub end_time: i64,
+    pub category: String,
+    pub resolution_mode: ResolutionMode,
+    pub resolution_source: String,
+    pub market_type: MarketType,
+    pub parent_market: Option<Pubkey>,
+    pub required_outcome: Option<Outcome>,
+}
+
+impl MarketDetails {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(self.question.len() <= 200, MarketError::QuestionTooLong);
+        require!(self.description.len() <= 500, MarketError::DescriptionTooLong);
+        require!(self.resolution_source.len() <= 200, MarketError::ResolutionSourceTooLong);
+        require!(self.end_time > now, MarketError::EndTimeInPast);
+        require!(self.end_time < now + 30 * 24 * 60 * 60, MarketError::EndTimeTooFar);
+        match &self.resolution_mode {
+            ResolutionMode::Manual => {}
+            ResolutionMode::PriceFeed(condition) => condition.validate()?,
+            ResolutionMode::Committee(committee) => committee.validate()?,
+            ResolutionMode::Reporting(params) => params.validate()?,
+        }
+        self.market_type.validate()?;
+        // Oracles and reporting rounds only answer YES/NO
+        if self.market_type != MarketType::Binary {
+            require!(
+                matches!(self.resolution_mode, ResolutionMode::Manual | ResolutionMode::Committee(_)),
+                MarketError::UnsupportedMarketType
+            );
+        }
+        // The parent is checked when the child is resolved by hand
+        if self.parent_market.is_some() {
+            require!(self.resolution_mode == ResolutionMode::Manual, MarketError::WrongResolutionMode);
+        }
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MarketProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    #[max_len(200)]
+    pub question: String,
+    #[max_len(500)]
+    pub description: String,
+    #[max_len(50)]
+    pub category: String,
+    #[max_len(200)]
+    pub resolution_source: String,
+    pub end_time: i64,
+    pub created_at: i64,
+    pub status: ProposalStatus,
+    pub market: Option<Pubkey>,      // Market created on approval
+    pub reviewed_by: Option<Pubkey>, // Curator who approved or rejected
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bet {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub tokens_received: u64,
+    pub outcome_index: u8, // Binary: 0 = YES, 1 = NO; categorical: label index; scalar: 0 = LONG, 1 = SHORT
+    pub timestamp: i64,
+    pub claimed: bool,
+    pub payout: u64,
+    pub index: u64, // Position among the market's bets
+
+    // Market pools right after this bet, the checkpoint for early resolution
+    pub yes_pool_after: u64,
+    pub no_pool_after: u64,
+    #[max_len(MAX_OUTCOMES)]
+    pub outcome_pools_after: Vec<u64>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserStats {
+    pub user: Pubkey,
+    pub total_bets: u64,
+    pub total_wagered: u64,
+    pub total_won: u64,
+    pub total_lost: u64,
+    pub win_count: u32,
+    pub loss_count: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub net_profit: i64, // Can be negative
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub proposed_outcome: Outcome,      // Outcome being challenged
+    pub counter_outcome: Outcome,       // Outcome put forward by the challenger
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub total_staked: u64,              // All juror stakes, revealed or not
+    pub proposed_stake: u64,            // Revealed stake backing the proposed outcome
+    pub counter_stake: u64,             // Revealed stake backing the counter outcome
+    pub juror_count: u32,
+    pub settled: bool,
+    pub winning_outcome: Option<Outcome>,
+    pub reward_pool: u64,               // Loser's bond plus forfeited stakes, shared by the majority
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Dispute {
+    /// Starts the juror vote on `market`'s proposed outcome; bumps are set by the caller
+    pub fn open(
+        &mut self,
+        market_key: Pubkey,
+        market: &Market,
+        counter_outcome: Outcome,
+        config: &Config,
+        now: i64,
+    ) -> Result<()> {
+        let commit_deadline = now
+            .checked_add(config.commit_period)
+            .ok_or(MarketError::MathOverflow)?;
+        let reveal_deadline = commit_deadline
+            .checked_add(config.reveal_period)
+            .ok_or(MarketError::MathOverflow)?;
+
+        self.market = market_key;
+        self.market_id = market.id;
+        self.proposed_outcome = market.outcome.ok_or(MarketError::NoResolutionProposed)?;
+        self.counter_outcome = counter_outcome;
+        self.commit_deadline = commit_deadline;
+        self.reveal_deadline = reveal_deadline;
+        self.total_staked = 0;
+        self.proposed_stake = 0;
+        self.counter_stake = 0;
+        self.juror_count = 0;
+        self.settled = false;
+        self.winning_outcome = None;
+        self.reward_pool = 0;
+        Ok(())
+    }
+
+    /// Stake-weighted majority; a tie keeps the proposed outcome
+    pub fn tally(&self) -> Outcome {
+        if self.counter_stake > self.proposed_stake {
+            self.counter_outcome
+        } else {
+            self.proposed_outcome
+        }
+    }
+
+    /// Revealed stake behind one of the contested outcomes
+    pub fn stake_for(&self, outcome: Outcome) -> u64 {
+        if outcome == self.proposed_outcome {
+            self.proposed_stake
+        } else if outcome == self.counter_outcome {
+            self.counter_stake
+        } else {
+            0
+        }
+    }
+
+    /// A majority juror's stake plus their pro-rata share of the reward pool
+    pub fn juror_payout(&self, stake: u64) -> Result<u64> {
+        let winning_outcome = self.winning_outcome.ok_or(MarketError::DisputeNotSettled)?;
+        let winning_stake = self.stake_for(winning_outcome);
+        let reward = (self.reward_pool as u128)
+            .checked_mul(stake as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(winning_stake as u128)
+            .ok_or(MarketError::MathOverflow)? as u64;
+        Ok(stake.checked_add(reward).ok_or(MarketError::MathOverflow)?)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct JurorVote {
+    pub dispute: Pubkey,
+    pub juror: Pubkey,
+    pub commitment: [u8; 32],  // hash(borsh(outcome) || salt || juror || market_id)
+    pub stake: u64,
+    pub vote: Option<Outcome>, // Set on reveal
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Reporting {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub reporting_deadline: i64,
+    pub slash_bps: u16,                 // Snapshot of the market's slash, 0 if nobody backed the result
+    pub yes_stake: u64,
+    pub no_stake: u64,
+    pub invalid_stake: u64,
+    pub total_staked: u64,
+    pub reporter_count: u32,
+    pub settled: bool,
+    pub winning_outcome: Option<Outcome>,
+    pub reward_pool: u64,               // Slashed minority stake, shared by the majority
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Reporting {
+    /// Starts the reporting round for `market`; bumps are set by the caller
+    pub fn open(&mut self, market_key: Pubkey, market: &Market, params: &ReportingParams) -> Result<()> {
+        self.market = market_key;
+        self.market_id = market.id;
+        self.reporting_deadline = market.end_time
+            .checked_add(params.reporting_period)
+            .ok_or(MarketError::MathOverflow)?;
+        self.slash_bps = params.slash_bps;
+        self.yes_stake = 0;
+        self.no_stake = 0;
+        self.invalid_stake = 0;
+        self.total_staked = 0;
+        self.reporter_count = 0;
+        self.settled = false;
+        self.winning_outcome = None;
+        self.reward_pool = 0;
+        Ok(())
+    }
+
+    pub fn add_stake(&mut self, outcome: Outcome, stake: u64) -> Result<()> {
+        let outcome_stake = match outcome {
+            Outcome::Yes => &mut self.yes_stake,
+            Outcome::No => &mut self.no_stake,
+            Outcome::Invalid => &mut self.invalid_stake,
+            Outcome::Category(_) | Outcome::Scalar(_) => return err!(MarketError::InvalidOutcome),
+        };
+        *outcome_stake = outcome_stake.checked_add(stake).ok_or(MarketError::MathOverflow)?;
+        self.total_staked = self.total_staked.checked_add(stake).ok_or(MarketError::MathOverflow)?;
+        self.reporter_count = self.reporter_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn stake_for(&self, outcome: Outcome) -> u64 {
+        match outcome {
+            Outcome::Yes => self.yes_stake,
+            Outcome::No => self.no_stake,
+            Outcome::Invalid => self.invalid_stake,
+            Outcome::Category(_) | Outcome::Scalar(_) => 0,
+        }
+    }
+
+    /// Stake-weighted majority; a tie at the top (or no reports) is Invalid
+    pub fn tally(&self) -> Outcome {
+        let (yes, no, invalid) = (self.yes_stake, self.no_stake, self.invalid_stake);
+        if yes > no && yes > invalid {
+            Outcome::Yes
+        } else if no > yes && no > invalid {
+            Outcome::No
+        } else {
+            Outcome::Invalid
+        }
+    }
+
+    /// Fixes the outcome and the slashed reward pool
+    pub fn settle(&mut self) -> Result<Outcome> {
+        let outcome = self.tally();
+        let winning_stake = self.stake_for(outcome);
+
+        // Nobody backed the result: nothing to reward, everyone gets their stake back
+        if winning_stake == 0 {
+            self.slash_bps = 0;
+        }
+        let minority_stake = self.total_staked
+            .checked_sub(winning_stake)
+            .ok_or(MarketError::MathOverflow)?;
+
+        self.reward_pool = (minority_stake as u128)
+            .checked_mul(self.slash_bps as u128)
+            .ok_or(MarketError::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(MarketError::MathOverflow)? as u64;
+        self.settled = true;
+        self.winning_outcome = Some(outcome);
+        Ok(outcome)
+    }
+
+    /// Settles the round without a result: nobody is slashed, every stake is returned
+    pub fn refund_all(&mut self) {
+        self.slash_bps = 0;
+        self.reward_pool = 0;
+        self.settled = true;
+        self.winning_outcome = Some(Outcome::Invalid);
+    }
+
+    /// Majority: stake plus a pro-rata share of the pool. Minority: stake minus the slash
+    /// (rounded in the vault's favour so the pool is always covered)
+    pub fn reporter_payout(&self, outcome: Outcome, stake: u64) -> Result<u64> {
+        let winning_outcome = self.winning_outcome.ok_or(MarketError::ReportsNotSettled)?;
+        if outcome == winning_outcome {
+            let reward = (self.reward_pool as u128)
+                .checked_mul(stake as u128)
+                .ok_or(MarketError::MathOverflow)?
+                .checked_div(self.stake_for(winning_outcome) as u128)
+                .ok_or(MarketError::MathOverflow)? as u64;
+            Ok(stake.checked_add(reward).ok_or(MarketError::MathOverflow)?)
+        } else {
+            let kept_bps = BPS_DENOMINATOR
+                .checked_sub(self.slash_bps as u64)
+                .ok_or(MarketError::MathOverflow)?;
+            let kept = (stake as u128)
+                .checked_mul(kept_bps as u128)
+                .ok_or(MarketError::MathOverflow)?
+                .checked_div(BPS_DENOMINATOR as u128)
+                .ok_or(MarketError::MathOverflow)?;
+            Ok(kept as u64)
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Report {
+    pub reporting: Pubkey,
+    pub reporter: Pubkey,
+    pub outcome: Outcome,
+    pub stake: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ParlayHouse {
+    pub max_payout: u64,            // Largest payout a single parlay may lock in
+    pub max_liability: u64,         // Cap on the payouts of all open parlays combined
+    pub outstanding_liability: u64, // Potential payouts of open parlays, reserved in the vault
+    pub max_leg_odds_bps: u64,      // Longest decimal odds a single leg may lock in
+    pub min_leg_pool: u64,          // Volume a leg market needs before it can be parlayed
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Parlay {
+    pub user: Pubkey,
+    pub stake: u64,
+    #[max_len(MAX_PARLAY_LEGS)]
+    pub legs: Vec<ParlayLeg>,
+    pub potential_payout: u64, // Stake times every leg's locked odds
+    pub placed_at: i64,
+    pub status: ParlayStatus,
+    pub payout: u64,
+    pub bump: u8,
+}
+
+impl Parlay {
+    /// Stake multiplied by each leg's odds, rounded down
+    pub fn payout_for(stake: u64, odds_bps: impl Iterator<Item = u64>) -> Result<u64> {
+        let mut payout = stake as u128;
+        for odds in odds_bps {
+            payout = payout
+                .checked_mul(odds as u128)
+                .ok_or(MarketError::MathOverflow)?
+                .checked_div(BPS_DENOMINATOR as u128)
+                .ok_or(MarketError::MathOverflow)?;
+        }
+        u64::try_from(payout).map_err(|_| error!(MarketError::MathOverflow))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ParlayLeg {
+    pub market: Pubkey,
+    pub outcome_index: u8,
+    pub odds_bps: u64, // Decimal odds locked at placement, 10_000 = even
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub market: Pubkey,
+    pub resolver: Pubkey,
+    pub outcome: Outcome,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RoleGrant {
+    pub role: Role,
+    pub wallet: Pubkey,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Multisig {
+    pub id: u64,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub action_count: u64,
+    pub authority_bump: u8, // Bump of the multisig_authority PDA
+    pub bump: u8,
+}
+
+impl Multisig {
+    pub fn signer_index(&self, key: &Pubkey) -> Result<usize> {
+        self.signers
+            .iter()
+            .position(|signer| signer == key)
+            .ok_or(error!(MarketError::NotMultisigSigner))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MultisigAction {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    #[max_len(MAX_ACTION_ACCOUNTS)]
+    pub accounts: Vec<ActionAccount>,
+    #[max_len(MAX_ACTION_DATA_LEN)]
+    pub data: Vec<u8>,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub approvals: Vec<bool>, // Indexed like Multisig.signers
+    pub executed: bool,
+    pub created_at: i64,
+}
 
-        msg!("Withdrawn {} fees from market {} to admin", fees, market_id);
-        Ok(())
-    }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ActionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
 }
 
-// ========== ACCOUNTS ==========
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum MarketStatus {
+    Active,
+    Resolved,
+    Cancelled,
+    Halted,
+    ResolutionProposed, // Outcome proposed, challenge window open
+    Disputed,           // Proposed outcome challenged, jurors voting
+}
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct CreateMarket<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + Market::INIT_SPACE,
-        seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Outcome {
+    Yes,
+    No,
+    Invalid,      // Question could not be answered, every bet is refunded
+    Category(u8), // Winning index of a categorical market
+    Scalar(i64),  // Reported value of a scalar or bucket market
+}
 
-    #[account(
-        init,
-        payer = creator,
-        token::mint = usdc_mint,
-        token::authority = vault,
-        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenAccount>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ParlayStatus {
+    Open,
+    Won,
+    Lost,
+    Refunded, // Every leg was voided, the stake is returned
+}
 
-    /// CHECK: USDC mint address
-    pub usdc_mint: AccountInfo<'info>,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegResult {
+    Open,
+    Won,
+    Lost,
+    Void, // Market voided or cancelled, the leg counts at even odds
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum ResolutionMode {
+    Manual,                     // Resolver role or bonded proposals
+    PriceFeed(PriceCondition),  // resolve_with_oracle against a Pyth or Switchboard feed
+    Committee(Committee),       // M-of-N attestations from named resolvers
+    Reporting(ReportingParams), // Stake-weighted reports after end_time
+}
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct Committee {
+    #[max_len(MAX_COMMITTEE_SIZE)]
+    pub resolvers: Vec<Pubkey>,
+    pub threshold: u8, // Matching attestations needed to resolve
 }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct PlaceBet<'info> {
-    #[account(
-        mut,
-        seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
+impl Committee {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.resolvers.is_empty() && self.resolvers.len() <= MAX_COMMITTEE_SIZE,
+            MarketError::InvalidCommittee
+        );
+        require!(
+            self.threshold > 0 && self.threshold as usize <= self.resolvers.len(),
+            MarketError::InvalidThreshold
+        );
+        for (i, resolver) in self.resolvers.iter().enumerate() {
+            require!(!self.resolvers[..i].contains(resolver), MarketError::InvalidCommittee);
+        }
+        Ok(())
+    }
+}
 
-    #[account(
-        init,
-        payer = user,
-        space = 8 + Bet::INIT_SPACE,
-        seeds = [b"bet", market.key().as_ref(), user.key().as_ref(), &market.total_bets_count.to_le_bytes()],
-        bump
-    )]
-    pub bet: Account<'info, Bet>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum MarketType {
+    Binary,                           // YES/NO on the virtual AMM pools
+    Categorical(CategoricalOutcomes), // One parimutuel pool per named outcome
+    Buckets(BucketBoundaries),        // Categorical over numeric ranges, won by the reported value
+    Scalar(ScalarRange),              // LONG/SHORT pools split by where the value lands
+}
 
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + UserStats::INIT_SPACE,
-        seeds = [b"user_stats", user.key().as_ref()],
-        bump
-    )]
-    pub user_stats: Account<'info, UserStats>,
+impl MarketType {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            MarketType::Binary => {}
+            MarketType::Categorical(outcomes) => outcomes.validate()?,
+            MarketType::Buckets(buckets) => buckets.validate()?,
+            MarketType::Scalar(range) => {
+                require!(range.lower < range.upper, MarketError::InvalidScalarRange);
+            }
+        }
+        Ok(())
+    }
+}
 
-    #[account(
-        mut,
-        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenAccount>,
+/// `n` increasing boundaries make `n + 1` contiguous buckets: below the first
+/// boundary, between each pair (lower bound inclusive) and from the last one up
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct BucketBoundaries {
+    #[max_len(MAX_OUTCOMES - 1)]
+    pub boundaries: Vec<i64>,
+}
 
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+impl BucketBoundaries {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.boundaries.is_empty() && self.boundaries.len() < MAX_OUTCOMES,
+            MarketError::InvalidBuckets
+        );
+        require!(
+            self.boundaries.windows(2).all(|pair| pair[0] < pair[1]),
+            MarketError::InvalidBuckets
+        );
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub fn count(&self) -> usize {
+        self.boundaries.len() + 1
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub fn bucket_of(&self, value: i64) -> u8 {
+        self.boundaries.iter().take_while(|boundary| value >= **boundary).count() as u8
+    }
 }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct ResolveMarket<'info> {
-    #[account(
-        mut,
-        seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
-
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ScalarRange {
+    pub lower: i64, // Value at or below which SHORT takes the whole pool
+    pub upper: i64, // Value at or above which LONG takes the whole pool
 }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct ClaimWinnings<'info> {
-    #[account(
-        seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct CategoricalOutcomes {
+    #[max_len(MAX_OUTCOMES, MAX_OUTCOME_LABEL_LEN)]
+    pub labels: Vec<String>,
+}
 
-    #[account(mut)]
-    pub bet: Account<'info, Bet>,
+impl CategoricalOutcomes {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.labels.len() >= 2 && self.labels.len() <= MAX_OUTCOMES,
+            MarketError::InvalidOutcomes
+        );
+        for label in &self.labels {
+            require!(
+                !label.is_empty() && label.len() <= MAX_OUTCOME_LABEL_LEN,
+                MarketError::InvalidOutcomes
+            );
+        }
+        Ok(())
+    }
+}
 
-    #[account(mut)]
-    pub user_stats: Account<'info, UserStats>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ReportingParams {
+    pub reporting_period: i64, // Seconds after end_time reports are accepted
+    pub slash_bps: u16,        // Share of minority stake paid to the majority
+}
 
-    #[account(
-        mut,
-        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenAccount>,
+impl ReportingParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.reporting_period > 0 && self.slash_bps as u64 <= BPS_DENOMINATOR,
+            MarketError::InvalidReportingParams
+        );
+        Ok(())
+    }
+}
 
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+/// Why a proposer picked an outcome: a link to the evidence and its sha256
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct ResolutionEvidence {
+    #[max_len(200)]
+    pub uri: String,
+    pub hash: [u8; 32],
+}
 
-    pub user: Signer<'info>,
+impl ResolutionEvidence {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.uri.len() <= 200, MarketError::EvidenceUriTooLong);
+        Ok(())
+    }
+}
 
-    pub token_program: Program<'info, Token>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
 }
 
-#[derive(Accounts)]
-pub struct CancelMarket<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    MarketCreator, // Creates and cancels markets
+    Resolver,      // Resolves markets
+    Treasurer,     // Withdraws protocol fees
+    Guardian,      // Pauses and unpauses the protocol
+}
 
-    pub creator: Signer<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseScope {
+    All,
+    Betting,
+    Claiming,
+    Creation,
+    FeeWithdrawal,
 }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct WithdrawFees<'info> {
-    #[account(
-        seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
+// ========== EVENTS ==========
 
-    #[account(
-        mut,
-        seeds = [b"vault", market_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenAccount>,
+#[event]
+pub struct ProtocolPaused {
+    pub scope: PauseScope,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub admin_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct ProtocolUnpaused {
+    pub scope: PauseScope,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
+#[event]
+pub struct MarketHalted {
+    pub market_id: u64,
+    pub reason_code: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct MarketResumed {
+    pub market_id: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
-// ========== DATA STRUCTURES ==========
+#[event]
+pub struct ResolutionProposed {
+    pub market_id: u64,
+    pub proposer: Pubkey,
+    pub outcome: Outcome,
+    pub bond: u64,
+    pub challenge_deadline: i64,
+    pub resolution_source: String,
+    pub evidence: ResolutionEvidence,
+    pub event_occurred_at: Option<i64>,
+}
 
-#[account]
-#[derive(InitSpace)]
-pub struct Market {
-    pub id: u64,
-    #[max_len(200)]
-    pub question: String,
-    #[max_len(500)]
-    pub description: String,
-    pub creator: Pubkey,
-    pub created_at: i64,
-    pub end_time: i64,
-    pub resolution_time: Option<i64>,
-    pub outcome: Option<bool>, // None = unresolved, Some(true) = YES won, Some(false) = NO won
-    #[max_len(50)]
-    pub category: String,
+#[event]
+pub struct ResolutionChallenged {
+    pub market_id: u64,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
 
-    // AMM pools
-    pub yes_pool: u64,  // Virtual YES tokens
-    pub no_pool: u64,   // Virtual NO tokens
+#[event]
+pub struct ResolutionFinalized {
+    pub market_id: u64,
+    pub outcome: Outcome,
+    pub timestamp: i64,
+}
 
-    // Stats
-    pub total_yes_bets: u64,
-    pub total_no_bets: u64,
-    pub total_volume: u64,
-    pub total_bets_count: u64,
-    pub unique_bettors: u64,
+#[event]
+pub struct AttestationSubmitted {
+    pub market_id: u64,
+    pub resolver: Pubkey,
+    pub outcome: Outcome,
+    pub timestamp: i64,
+}
 
-    pub fee_percentage: u8,
-    pub status: MarketStatus,
-    pub vault: Pubkey,
-    pub vault_bump: u8,
+#[event]
+pub struct MarketVoided {
+    pub market_id: u64,
+    pub resolution_deadline: i64,
+    pub timestamp: i64,
+}
 
-    // Decentralized resolution
-    pub resolution_proposer: Option<Pubkey>,  // Who proposed the current resolution
-    pub resolution_bond: u64,                  // Current bond amount staked
-    pub challenge_deadline: Option<i64>,       // When challenge period ends
-    pub is_finalized: bool,                    // True after challenge period with no disputes
-    pub resolver: Pubkey,                      // Deprecated, kept for compatibility
+#[event]
+pub struct ReportSubmitted {
+    pub market_id: u64,
+    pub reporter: Pubkey,
+    pub outcome: Outcome,
+    pub stake: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Bet {
-    pub market: Pubkey,
+#[event]
+pub struct ReportsSettled {
+    pub market_id: u64,
+    pub outcome: Outcome,
+    pub winning_stake: u64,
+    pub reward_pool: u64,
+}
+
+#[event]
+pub struct ParlayPlaced {
+    pub parlay: Pubkey,
     pub user: Pubkey,
-    pub amount: u64,
-    pub tokens_received: u64,
-    pub prediction: bool, // true = YES, false = NO
-    pub timestamp: i64,
-    pub claimed: bool,
-    pub payout: u64,
+    pub stake: u64,
+    pub legs: u8,
+    pub potential_payout: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct UserStats {
+#[event]
+pub struct ParlaySettled {
+    pub parlay: Pubkey,
     pub user: Pubkey,
-    pub total_bets: u64,
-    pub total_wagered: u64,
-    pub total_won: u64,
-    pub total_lost: u64,
-    pub win_count: u32,
-    pub loss_count: u32,
-    pub current_streak: u32,
-    pub best_streak: u32,
-    pub net_profit: i64, // Can be negative
+    pub status: ParlayStatus,
+    pub payout: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum MarketStatus {
-    Active,
-    Resolved,
-    Cancelled,
+#[event]
+pub struct DisputeSettled {
+    pub market_id: u64,
+    pub outcome: Outcome,
+    pub winning_stake: u64,
+    pub reward_pool: u64,
 }
 
 // ========== ERRORS ==========
@@ -590,9 +4963,9 @@ pub enum MarketError {
     MarketNotEnded,
     #[msg("Market already ended")]
     MarketEnded,
-    #[msg("Bet too small (minimum 1 USDC)")]
+    #[msg("Bet below the configured minimum")]
     BetTooSmall,
-    #[msg("Bet too large (maximum 10,000 USDC)")]
+    #[msg("Bet above the configured maximum")]
     BetTooLarge,
     #[msg("Unauthorized resolver")]
     UnauthorizedResolver,
@@ -612,4 +4985,158 @@ pub enum MarketError {
     Unauthorized,
     #[msg("No fees available to withdraw")]
     NoFeesToWithdraw,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Invalid bet limits")]
+    InvalidBetLimits,
+    #[msg("Mint does not match the configured collateral mint")]
+    InvalidCollateralMint,
+    #[msg("Token account is not owned by the fee recipient")]
+    InvalidFeeRecipient,
+    #[msg("No pending admin transfer")]
+    NoPendingAdmin,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Invalid multisig signer set")]
+    InvalidMultisigSigners,
+    #[msg("Invalid multisig threshold")]
+    InvalidThreshold,
+    #[msg("Signer is not part of the multisig")]
+    NotMultisigSigner,
+    #[msg("Multisig action has too many accounts or too much data")]
+    ActionTooLarge,
+    #[msg("Multisig action already executed")]
+    ActionAlreadyExecuted,
+    #[msg("Not enough approvals to execute")]
+    NotEnoughApprovals,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Market is halted")]
+    MarketHalted,
+    #[msg("Market is not halted")]
+    MarketNotHalted,
+    #[msg("Creator token account required to post the creation bond")]
+    MissingBondTokenAccount,
+    #[msg("Market has no creation bond to settle")]
+    NoCreationBond,
+    #[msg("Bond destination is not owned by the expected wallet")]
+    InvalidBondDestination,
+    #[msg("Proposal is not pending")]
+    ProposalNotPending,
+    #[msg("Resolution periods must be positive")]
+    InvalidChallengePeriod,
+    #[msg("No resolution has been proposed")]
+    NoResolutionProposed,
+    #[msg("Challenge period has ended")]
+    ChallengePeriodEnded,
+    #[msg("Challenge period is still running")]
+    ChallengePeriodActive,
+    #[msg("Market resolution is not disputed")]
+    MarketNotDisputed,
+    #[msg("Market resolution is not finalized")]
+    ResolutionNotFinalized,
+    #[msg("Juror stake below the configured minimum")]
+    StakeTooSmall,
+    #[msg("Bond holders cannot vote on their own dispute")]
+    ConflictedJuror,
+    #[msg("Commit period has ended")]
+    CommitPeriodEnded,
+    #[msg("Commit period is still running")]
+    CommitPeriodActive,
+    #[msg("Reveal period has ended")]
+    RevealPeriodEnded,
+    #[msg("Reveal period is still running")]
+    RevealPeriodActive,
+    #[msg("Vote already revealed")]
+    AlreadyRevealed,
+    #[msg("Revealed vote does not match the commitment")]
+    InvalidReveal,
+    #[msg("Dispute has not been settled")]
+    DisputeNotSettled,
+    #[msg("Juror did not vote with the majority")]
+    NotWinningJuror,
+    #[msg("Counter outcome must differ from the proposed outcome")]
+    SameOutcome,
+    #[msg("Vote must back the proposed or the counter outcome")]
+    InvalidVote,
+    #[msg("Market cannot be resolved this way")]
+    WrongResolutionMode,
+    #[msg("Invalid price condition")]
+    InvalidPriceCondition,
+    #[msg("Price feed account is not a valid price update")]
+    InvalidPriceFeed,
+    #[msg("Price is stale")]
+    StalePrice,
+    #[msg("Price confidence interval too wide")]
+    PriceTooUncertain,
+    #[msg("Invalid resolver committee")]
+    InvalidCommittee,
+    #[msg("Signer is not on the market's resolver committee")]
+    NotCommitteeMember,
+    #[msg("Dispute accounts required to record a conflicting attestation")]
+    MissingDisputeAccounts,
+    #[msg("Resolution deadline has not passed")]
+    ResolutionDeadlineNotReached,
+    #[msg("Resolution source too long (max 200 characters)")]
+    ResolutionSourceTooLong,
+    #[msg("Evidence URI too long (max 200 characters)")]
+    EvidenceUriTooLong,
+    #[msg("Event time must fall between market creation and now")]
+    InvalidEventTime,
+    #[msg("Invalid reporting period or slash")]
+    InvalidReportingParams,
+    #[msg("Reporting period has ended")]
+    ReportingPeriodEnded,
+    #[msg("Reporting period still active")]
+    ReportingPeriodActive,
+    #[msg("Reports not settled yet")]
+    ReportsNotSettled,
+    #[msg("Categorical markets need 2 to 8 outcomes with labels of up to 32 characters")]
+    InvalidOutcomes,
+    #[msg("Outcome does not exist in this market")]
+    InvalidOutcome,
+    #[msg("Resolution mode not supported for this market type")]
+    UnsupportedMarketType,
+    #[msg("Scalar lower bound must be below the upper bound")]
+    InvalidScalarRange,
+    #[msg("Buckets need 1 to 7 strictly increasing boundaries")]
+    InvalidBuckets,
+    #[msg("Parent market account required for a conditional market")]
+    MissingParentMarket,
+    #[msg("Account is not this market's parent")]
+    InvalidParentMarket,
+    #[msg("Parent market not resolved yet")]
+    ParentNotResolved,
+    #[msg("Parlay max payout must be positive and within the max liability, leg odds above even")]
+    InvalidParlayLimits,
+    #[msg("Funding amount must be positive")]
+    InvalidFundingAmount,
+    #[msg("Parlays need 2 to 5 distinct leg markets, passed in leg order")]
+    InvalidParlayLegs,
+    #[msg("Leg outcome has no liquidity to price odds from")]
+    NoParlayOdds,
+    #[msg("Parlay payout above the house maximum")]
+    ParlayPayoutTooLarge,
+    #[msg("Parlay house cannot cover the liability")]
+    ParlayLiabilityExceeded,
+    #[msg("Parlay already settled")]
+    ParlayAlreadySettled,
+    #[msg("Parlay legs still open")]
+    ParlayLegsOpen,
+    #[msg("Claim window still open and bets left unclaimed")]
+    ClaimWindowOpen,
+    #[msg("Claim window closed, the vault has been swept")]
+    ClaimWindowClosed,
+    #[msg("Bet belongs to a different market")]
+    BetMarketMismatch,
+    #[msg("Checkpoint must be the last bet before the event, followed by the first bet after it")]
+    InvalidBetCheckpoint,
+    #[msg("Leg market volume below the parlay house minimum")]
+    ParlayLegPoolTooSmall,
+    #[msg("Leg odds above the parlay house maximum")]
+    ParlayLegOddsTooHigh,
+    #[msg("Dispute accounts only accepted with a conflicting attestation")]
+    UnexpectedDisputeAccounts,
+    #[msg("Reports already settled")]
+    ReportsAlreadySettled,
 }
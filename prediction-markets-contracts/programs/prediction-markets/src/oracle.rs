@@ -0,0 +1,364 @@
+// Price feed adapters for oracle-resolved markets
+//
+// Each supported oracle is decoded straight from its account data into an
+// OraclePrice, so the program does not pull in the oracle SDKs (and their
+// Solana version pins). Markets name the source in their PriceCondition.
+
+use anchor_lang::prelude::*;
+
+use crate::{MarketError, BPS_DENOMINATOR};
+
+// Owners of Pyth PriceUpdateV2 accounts: the pull receiver and the sponsored push feeds
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+pub const PYTH_PUSH_ORACLE_PROGRAM_ID: Pubkey = pubkey!("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
+
+// Owner of Switchboard on-demand PullFeedAccountData accounts
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+// sha256("account:PriceUpdateV2")[..8]
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+// sha256("account:PullFeedAccountData")[..8]
+const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+// PullFeedAccountData offsets (including the discriminator)
+const PULL_FEED_LAST_UPDATE_OFFSET: usize = 2216; // last_update_timestamp: i64
+const PULL_FEED_RESULT_OFFSET: usize = 2264;      // result.value: i128, then result.std_dev: i128
+
+// Switchboard results are fixed point with 18 decimals
+const SWITCHBOARD_EXPO: i32 = -18;
+
+// Widest exponent gap we are willing to rescale across
+const MAX_EXPO_DIFF: u32 = 18;
+
+/// A price as published by the feed: `price * 10^expo`, +/- `conf * 10^expo`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i128,
+    pub conf: u128,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum OracleSource {
+    Pyth,        // PriceUpdateV2 account (pull or sponsored push feed)
+    Switchboard, // On-demand PullFeedAccountData account
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Comparator {
+    Above,     // price > threshold
+    AtOrAbove, // price >= threshold
+    Below,     // price < threshold
+    AtOrBelow, // price <= threshold
+}
+
+/// "YES if the feed's price is <comparator> threshold * 10^threshold_expo"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct PriceCondition {
+    pub source: OracleSource,
+    pub feed: Pubkey,            // Price account read by resolve_with_oracle
+    pub feed_id: [u8; 32],       // Pyth feed id the update must carry (unused for Switchboard)
+    pub threshold: i64,
+    pub threshold_expo: i32,
+    pub comparator: Comparator,
+    pub max_staleness: u32,      // Seconds after end_time the settlement price must be published within
+    pub max_confidence_bps: u16, // Max confidence interval relative to the price
+}
+
+impl PriceCondition {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.max_staleness > 0, MarketError::InvalidPriceCondition);
+        require!(
+            self.source != OracleSource::Pyth || self.feed_id != [0; 32],
+            MarketError::InvalidPriceCondition
+        );
+        require!(
+            self.max_confidence_bps as u64 <= BPS_DENOMINATOR,
+            MarketError::InvalidPriceCondition
+        );
+        Ok(())
+    }
+
+    /// Outcome of the condition for the price at the market's close
+    pub fn evaluate(&self, price: &OraclePrice, end_time: i64) -> Result<bool> {
+        // Must be published within max_staleness after the close, so a caller
+        // cannot wait for a favourable tick
+        require!(price.publish_time >= end_time, MarketError::StalePrice);
+        require!(
+            price.publish_time <= end_time.saturating_add(self.max_staleness as i64),
+            MarketError::StalePrice
+        );
+
+        // conf / |price| <= max_confidence_bps / 10_000
+        require!(price.price != 0, MarketError::InvalidPriceFeed);
+        let max_conf = price.price.unsigned_abs()
+            .checked_mul(self.max_confidence_bps as u128)
+            .ok_or(MarketError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        require!(price.conf <= max_conf, MarketError::PriceTooUncertain);
+
+        let (price_value, threshold_value) =
+            rescale(price.price, price.expo, self.threshold as i128, self.threshold_expo)?;
+        Ok(match self.comparator {
+            Comparator::Above => price_value > threshold_value,
+            Comparator::AtOrAbove => price_value >= threshold_value,
+            Comparator::Below => price_value < threshold_value,
+            Comparator::AtOrBelow => price_value <= threshold_value,
+        })
+    }
+}
+
+/// Brings two fixed-point values to the smaller of their exponents
+fn rescale(a: i128, a_expo: i32, b: i128, b_expo: i32) -> Result<(i128, i128)> {
+    let diff = a_expo.abs_diff(b_expo);
+    require!(diff <= MAX_EXPO_DIFF, MarketError::InvalidPriceCondition);
+    let factor = 10i128.pow(diff);
+    if a_expo > b_expo {
+        Ok((a.checked_mul(factor).ok_or(MarketError::MathOverflow)?, b))
+    } else {
+        Ok((a, b.checked_mul(factor).ok_or(MarketError::MathOverflow)?))
+    }
+}
+
+/// Reads the latest price from the condition's feed account, checking its owner
+pub fn load_price(condition: &PriceCondition, feed: &AccountInfo) -> Result<OraclePrice> {
+    let data = feed.try_borrow_data()?;
+    match condition.source {
+        OracleSource::Pyth => {
+            require!(
+                *feed.owner == PYTH_RECEIVER_PROGRAM_ID || *feed.owner == PYTH_PUSH_ORACLE_PROGRAM_ID,
+                MarketError::InvalidPriceFeed
+            );
+            decode_pyth_price_update(&data, &condition.feed_id)
+        }
+        OracleSource::Switchboard => {
+            require!(*feed.owner == SWITCHBOARD_ON_DEMAND_PROGRAM_ID, MarketError::InvalidPriceFeed);
+            decode_switchboard_pull_feed(&data)
+        }
+    }
+}
+
+/// Decodes a PriceUpdateV2 account:
+/// discriminator (8) | write_authority (32) | verification_level (1 or 2) |
+/// feed_id (32) | price i64 | conf u64 | exponent i32 | publish_time i64 | ...
+/// Only fully verified updates for `feed_id` are accepted: the write authority
+/// of a pull account can post any feed into it
+pub fn decode_pyth_price_update(data: &[u8], feed_id: &[u8; 32]) -> Result<OraclePrice> {
+    require!(
+        data.len() >= 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        MarketError::InvalidPriceFeed
+    );
+    let mut offset = 8 + 32;
+
+    // VerificationLevel::Partial { num_signatures: u8 } = 0, VerificationLevel::Full = 1
+    require!(data.get(offset) == Some(&1), MarketError::InvalidPriceFeed);
+    offset += 1;
+
+    require!(
+        data.get(offset..offset + 32) == Some(feed_id.as_ref()),
+        MarketError::InvalidPriceFeed
+    );
+    offset += 32;
+    let price = read_i64(data, &mut offset)?;
+    let conf = read_i64(data, &mut offset)? as u64;
+    let expo = read_i32(data, &mut offset)?;
+    let publish_time = read_i64(data, &mut offset)?;
+
+    Ok(OraclePrice {
+        price: price as i128,
+        conf: conf as u128,
+        expo,
+        publish_time,
+    })
+}
+
+/// Decodes the current result of a Switchboard on-demand PullFeedAccountData:
+/// result.value is the median of the oracle submissions and result.std_dev
+/// their spread, used as the confidence interval
+pub fn decode_switchboard_pull_feed(data: &[u8]) -> Result<OraclePrice> {
+    require!(
+        data.len() >= 8 && data[..8] == PULL_FEED_DISCRIMINATOR,
+        MarketError::InvalidPriceFeed
+    );
+
+    let mut offset = PULL_FEED_LAST_UPDATE_OFFSET;
+    let publish_time = read_i64(data, &mut offset)?;
+
+    let mut offset = PULL_FEED_RESULT_OFFSET;
+    let price = read_i128(data, &mut offset)?;
+    let std_dev = read_i128(data, &mut offset)?;
+
+    Ok(OraclePrice {
+        price,
+        conf: std_dev.unsigned_abs(),
+        expo: SWITCHBOARD_EXPO,
+        publish_time,
+    })
+}
+
+fn read_i64(data: &[u8], offset: &mut usize) -> Result<i64> {
+    let bytes = data
+        .get(*offset..*offset + 8)
+        .ok_or(MarketError::InvalidPriceFeed)?;
+    *offset += 8;
+    Ok(i64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i128(data: &[u8], offset: &mut usize) -> Result<i128> {
+    let bytes = data
+        .get(*offset..*offset + 16)
+        .ok_or(MarketError::InvalidPriceFeed)?;
+    *offset += 16;
+    Ok(i128::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i32(data: &[u8], offset: &mut usize) -> Result<i32> {
+    let bytes = data
+        .get(*offset..*offset + 4)
+        .ok_or(MarketError::InvalidPriceFeed)?;
+    *offset += 4;
+    Ok(i32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED_ID: [u8; 32] = [9; 32];
+
+    // SOL/USD style feed: expo -8
+    fn price_update(verification_level: &[u8], price: i64, conf: u64, publish_time: i64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7; 32]); // write_authority
+        data.extend_from_slice(verification_level);
+        data.extend_from_slice(&FEED_ID);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&(-8i32).to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&(publish_time - 1).to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&price.to_le_bytes()); // ema_price
+        data.extend_from_slice(&conf.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&42u64.to_le_bytes()); // posted_slot
+        data
+    }
+
+    // Zeroed PullFeedAccountData with only the fields we read filled in
+    fn pull_feed(value: i128, std_dev: i128, last_update: i64) -> Vec<u8> {
+        let mut data = vec![0u8; 3208];
+        data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+        data[PULL_FEED_LAST_UPDATE_OFFSET..PULL_FEED_LAST_UPDATE_OFFSET + 8]
+            .copy_from_slice(&last_update.to_le_bytes());
+        data[PULL_FEED_RESULT_OFFSET..PULL_FEED_RESULT_OFFSET + 16].copy_from_slice(&value.to_le_bytes());
+        data[PULL_FEED_RESULT_OFFSET + 16..PULL_FEED_RESULT_OFFSET + 32].copy_from_slice(&std_dev.to_le_bytes());
+        data
+    }
+
+    fn condition(comparator: Comparator) -> PriceCondition {
+        PriceCondition {
+            source: OracleSource::Pyth,
+            feed: Pubkey::default(),
+            feed_id: FEED_ID,
+            threshold: 150,
+            threshold_expo: 0, // $150
+            comparator,
+            max_staleness: 60,
+            max_confidence_bps: 100, // 1%
+        }
+    }
+
+    #[test]
+    fn decodes_fully_verified_update() {
+        let data = price_update(&[1], 151_2500_0000, 5_000_000, 1_000);
+        let price = decode_pyth_price_update(&data, &FEED_ID).unwrap();
+        assert_eq!(
+            price,
+            OraclePrice {
+                price: 151_2500_0000,
+                conf: 5_000_000,
+                expo: -8,
+                publish_time: 1_000,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_partial_verification_and_foreign_accounts() {
+        let partial = price_update(&[0, 3], 151_0000_0000, 0, 1_000);
+        assert!(decode_pyth_price_update(&partial, &FEED_ID).is_err());
+
+        let mut foreign = price_update(&[1], 151_0000_0000, 0, 1_000);
+        foreign[0] ^= 1;
+        assert!(decode_pyth_price_update(&foreign, &FEED_ID).is_err());
+
+        let truncated = price_update(&[1], 151_0000_0000, 0, 1_000);
+        assert!(decode_pyth_price_update(&truncated[..80], &FEED_ID).is_err());
+
+        // A different feed posted into the same account
+        let other_feed = price_update(&[1], 151_0000_0000, 0, 1_000);
+        assert!(decode_pyth_price_update(&other_feed, &[8; 32]).is_err());
+    }
+
+    #[test]
+    fn decodes_switchboard_pull_feed() {
+        // 98.5 with a 0.1 spread, 18 decimals
+        let data = pull_feed(98_500_000_000_000_000_000, 100_000_000_000_000_000, 2_000);
+        let price = decode_switchboard_pull_feed(&data).unwrap();
+        assert_eq!(
+            price,
+            OraclePrice {
+                price: 98_500_000_000_000_000_000,
+                conf: 100_000_000_000_000_000,
+                expo: -18,
+                publish_time: 2_000,
+            }
+        );
+
+        // YES if the score is at or below 100
+        let cond = PriceCondition {
+            source: OracleSource::Switchboard,
+            threshold: 100,
+            ..condition(Comparator::AtOrBelow)
+        };
+        assert!(cond.evaluate(&price, 1_990).unwrap());
+
+        let mut foreign = data.clone();
+        foreign[0] ^= 1;
+        assert!(decode_switchboard_pull_feed(&foreign).is_err());
+        assert!(decode_switchboard_pull_feed(&data[..2270]).is_err());
+    }
+
+    #[test]
+    fn compares_across_exponents() {
+        let price = OraclePrice { price: 150_0000_0000, conf: 0, expo: -8, publish_time: 1_000 };
+
+        assert!(!condition(Comparator::Above).evaluate(&price, 1_000).unwrap());
+        assert!(condition(Comparator::AtOrAbove).evaluate(&price, 1_000).unwrap());
+        assert!(!condition(Comparator::Below).evaluate(&price, 1_000).unwrap());
+        assert!(condition(Comparator::AtOrBelow).evaluate(&price, 1_000).unwrap());
+
+        let higher = OraclePrice { price: 150_0000_0001, ..price };
+        assert!(condition(Comparator::Above).evaluate(&higher, 1_000).unwrap());
+    }
+
+    #[test]
+    fn rejects_stale_and_uncertain_prices() {
+        let cond = condition(Comparator::Above);
+
+        // Published before the market closed
+        let early = OraclePrice { price: 160_0000_0000, conf: 0, expo: -8, publish_time: 999 };
+        assert!(cond.evaluate(&early, 1_000).is_err());
+
+        // Published too long after the close
+        let late = OraclePrice { publish_time: 1_061, ..early };
+        assert!(cond.evaluate(&late, 1_000).is_err());
+        let on_time = OraclePrice { publish_time: 1_060, ..early };
+        assert!(cond.evaluate(&on_time, 1_000).unwrap());
+
+        // Confidence wider than 1% of the price
+        let uncertain = OraclePrice { conf: 2_0000_0000, ..on_time };
+        assert!(cond.evaluate(&uncertain, 1_000).is_err());
+    }
+}
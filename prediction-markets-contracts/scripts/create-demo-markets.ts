@@ -78,7 +78,12 @@ async function createDemoMarkets() {
           market.question,
           market.description,
           new anchor.BN(market.endTime),
-          market.category
+          market.category,
+          null, // Protocol default fee
+          { manual: {} },
+          "Resolved by the Solana Saga team from public sources",
+          { binary: {} },
+          null // Not conditional
         )
         .accounts({
           usdcMint: USDC_DEVNET_MINT,
@@ -5,7 +5,7 @@
  * 1. Creator creates a market
  * 2. Multiple users place YES/NO bets
  * 3. Market ends
- * 4. Creator resolves the market
+ * 4. Creator proposes the outcome and finalizes it after the challenge period
  * 5. Winners claim their winnings
  * 6. Check leaderboard and stats
  *
@@ -134,7 +134,7 @@ async function main() {
 
   try {
     const tx = await program.methods
-      .createMarket(marketId, question, description, endTime, category)
+      .createMarket(marketId, question, description, endTime, category, null, { manual: {} }, "Resolved manually by the test creator", { binary: {} }, null)
       .accounts({
         market: marketPda,
         vault: vaultPda,
@@ -188,9 +188,9 @@ async function main() {
   } else {
     // Place multiple bets to simulate different users
     const bets = [
-      { amount: 5, prediction: true, label: "YES bet (5 USDC)" },
-      { amount: 3, prediction: false, label: "NO bet (3 USDC)" },
-      { amount: 2, prediction: true, label: "YES bet (2 USDC)" },
+      { amount: 5, outcomeIndex: 0, label: "YES bet (5 USDC)" },
+      { amount: 3, outcomeIndex: 1, label: "NO bet (3 USDC)" },
+      { amount: 2, outcomeIndex: 0, label: "YES bet (2 USDC)" },
     ];
 
     for (let i = 0; i < bets.length; i++) {
@@ -210,7 +210,7 @@ async function main() {
 
       try {
         const tx = await program.methods
-          .placeBet(marketId, new anchor.BN(bet.amount * 1e6), bet.prediction)
+          .placeBet(marketId, new anchor.BN(bet.amount * 1e6), bet.outcomeIndex)
           .accounts({
             market: marketPda,
             bet: betPda,
@@ -230,7 +230,7 @@ async function main() {
         // Verify bet
         const betData = await program.account.bet.fetch(betPda);
         console.log(`   - Amount: ${formatUSDC(betData.amount)} USDC`);
-        console.log(`   - Prediction: ${betData.prediction ? "YES" : "NO"}`);
+        console.log(`   - Prediction: ${betData.outcomeIndex === 0 ? "YES" : "NO"}`);
         console.log(`   - Claimed: ${betData.claimed}\n`);
 
         await sleep(1000); // Wait 1 second between bets
@@ -279,25 +279,44 @@ async function main() {
 
   // Decide outcome (YES wins in this test)
   const outcome = true; // YES wins
+  const evidence = { uri: "", hash: new Array(32).fill(0) };
   console.log(`\n🎯 Resolving market as: ${outcome ? "YES" : "NO"} wins`);
 
   try {
     const tx = await program.methods
-      .resolveMarket(marketId, outcome)
+      .resolveMarket(marketId, outcome ? { yes: {} } : { no: {} }, evidence, null)
       .accounts({
         market: marketPda,
-        creator: creator.publicKey,
+        resolver: creator.publicKey,
       })
       .rpc();
 
-    console.log("\n✅ Market resolved successfully!");
+    console.log("\n✅ Resolution proposed!");
     console.log("   TX:", tx.slice(0, 16) + "...");
 
+    // Outcome only becomes claimable once the challenge period is over
+    const proposedMarket = await program.account.market.fetch(marketPda);
+    const challengeDeadline = toNum(proposedMarket.challengeDeadline);
+    const waitSeconds = Math.max(0, challengeDeadline - Math.floor(Date.now() / 1000)) + 5;
+    console.log(`⏳ Waiting ${waitSeconds} seconds for the challenge period to end...`);
+    await sleep(waitSeconds * 1000);
+
+    const finalizeTx = await program.methods
+      .finalizeResolution(marketId)
+      .accounts({
+        market: marketPda,
+        proposerTokenAccount: creatorTokenAccount,
+      })
+      .rpc();
+
+    console.log("\n✅ Market resolved successfully!");
+    console.log("   TX:", finalizeTx.slice(0, 16) + "...");
+
     // Verify resolution
     const resolvedMarket = await program.account.market.fetch(marketPda);
     console.log("\n   Market status after resolution:");
     console.log("   - Status:", Object.keys(resolvedMarket.status)[0]);
-    console.log("   - Outcome:", resolvedMarket.outcome ? "YES" : "NO");
+    console.log("   - Outcome:", Object.keys(resolvedMarket.outcome)[0].toUpperCase());
     console.log("   - Is Finalized:", resolvedMarket.isFinalized);
     console.log("   - Resolution Time:", new Date(toNum(resolvedMarket.resolutionTime) * 1000).toLocaleString());
   } catch (error) {
@@ -331,12 +350,12 @@ async function main() {
       const bet = await program.account.bet.fetch(betPda);
       betsChecked++;
 
-      const isWinner = bet.prediction === outcome;
+      const isWinner = (bet.outcomeIndex === 0) === outcome;
       const amount = formatUSDC(bet.amount);
 
       console.log(`Bet ${i + 1} (Market bet #${i}):`);
       console.log(`   - Amount: ${amount} USDC`);
-      console.log(`   - Prediction: ${bet.prediction ? "YES" : "NO"}`);
+      console.log(`   - Prediction: ${bet.outcomeIndex === 0 ? "YES" : "NO"}`);
       console.log(`   - Result: ${isWinner ? "✅ WON" : "❌ LOST"}`);
       console.log(`   - Claimed: ${bet.claimed}`);
 
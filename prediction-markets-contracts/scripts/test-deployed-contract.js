@@ -163,7 +163,7 @@ async function main() {
         const data = bet.account;
         console.log(`\n   Bet ${i + 1}:`);
         console.log(`   - Amount: ${(toNum(data.amount) / 1e6).toFixed(2)} USDC`);
-        console.log(`   - Prediction: ${data.prediction ? "YES" : "NO"}`);
+        console.log(`   - Prediction: ${data.outcomeIndex === 0 ? "YES" : "NO"}`);
         console.log(`   - Claimed: ${data.claimed}`);
         console.log(`   - Timestamp: ${new Date(toNum(data.timestamp) * 1000).toLocaleString()}`);
         console.log(`   - Bet PDA: ${bet.publicKey.toString()}`);
@@ -178,7 +178,7 @@ async function main() {
     console.log(`   - End time: ${new Date(endTime.toNumber() * 1000).toLocaleString()}`);
 
     const tx = await program.methods
-      .createMarket(marketId, question, description, endTime, category)
+      .createMarket(marketId, question, description, endTime, category, null, { manual: {} }, "Resolved manually by the test creator", { binary: {} }, null)
       .accounts({
         market: marketPda,
         vault: vaultPda,
@@ -251,7 +251,7 @@ async function main() {
         const data = bet.account as any;
         console.log(`\n   Bet ${i + 1}:`);
         console.log(`   - Amount: ${(data.amount.toNumber() / 1e6).toFixed(2)} USDC`);
-        console.log(`   - Prediction: ${data.prediction ? "YES" : "NO"}`);
+        console.log(`   - Prediction: ${data.outcomeIndex === 0 ? "YES" : "NO"}`);
         console.log(`   - Claimed: ${data.claimed}`);
         console.log(`   - Timestamp: ${new Date(data.timestamp.toNumber() * 1000).toLocaleString()}`);
       });
@@ -16,6 +16,7 @@ import {
   getAccount,
 } from "@solana/spl-token";
 import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
 
 describe("Prediction Markets - Comprehensive Test Suite", () => {
   // Configure the client to use the local cluster
@@ -38,7 +39,180 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
   let marketPda: PublicKey;
   let vaultPda: PublicKey;
 
+  // Upgradeable loader, owner of the program data account
+  const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
   // Helper function to get PDA
+  function getConfigPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+  }
+
+  function getProgramDataPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    );
+  }
+
+  function getRoleGrantPda(role: number, wallet: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([role]), wallet.toBuffer()],
+      program.programId
+    );
+  }
+
+  // Role discriminants, in declaration order of the on-chain enum
+  const ROLE_MARKET_CREATOR = 0;
+  const ROLE_RESOLVER = 1;
+  const ROLE_GUARDIAN = 3;
+
+  // Outcome enum values as the client encodes them
+  const YES = { yes: {} };
+  const NO = { no: {} };
+  const INVALID = { invalid: {} };
+  const OUTCOME_INDEX = { yes: 0, no: 1, invalid: 2 };
+
+  // Resolution modes
+  const MANUAL = { manual: {} };
+  const RESOLUTION_SOURCE = "Official results published by the organiser";
+  const NO_EVIDENCE = { uri: "", hash: new Array(32).fill(0) };
+
+  // Market types and binary bet outcome indexes
+  const BINARY = { binary: {} };
+  const BET_YES = 0;
+  const BET_NO = 1;
+
+  // Fabricated Pyth SOL/USD update loaded by the local validator (see Anchor.toml)
+  const PYTH_SOL_USD_FEED = new PublicKey("5PK24iydZDUmuv2fiEZbFqR5MdLZqe1vufrWCa8ywVj8");
+  // Fabricated Switchboard on-demand feed reporting 98.5
+  const SWITCHBOARD_FEED = new PublicKey("5vxfXScxPbgzXVCZ9pS9BJrBWbWoZsT2PFZFVnRugCea");
+  // Pyth SOL/USD feed id carried by the fabricated update
+  const SOL_USD_FEED_ID = Array.from(
+    Buffer.from("ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d", "hex")
+  );
+  // Both fixtures are published in 2100, so oracle test markets accept prices
+  // published up to u32::MAX seconds after their close
+  const FIXTURE_PUBLISH_WINDOW = 4_294_967_295;
+
+  function getMultisigPda(multisigId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig"), multisigId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getMultisigAuthorityPda(multisigPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig_authority"), multisigPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getMultisigActionPda(multisigPda: PublicKey, index: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig_action"), multisigPda.toBuffer(), index.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getBondEscrowPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_escrow")],
+      program.programId
+    );
+  }
+
+  function getProposalPda(proposalId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), proposalId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getDisputePda(marketId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getDisputeVaultPda(marketId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_vault"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getReportingPda(marketId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("reporting"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getReportingVaultPda(marketId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("reporting_vault"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getReportPda(marketId: BN, reporter: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("report"), marketId.toArrayLike(Buffer, "le", 8), reporter.toBuffer()],
+      program.programId
+    );
+  }
+
+  function getParlayPda(user: PublicKey, parlayId: BN): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("parlay"), user.toBuffer(), parlayId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+
+  function getParlayHousePda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([Buffer.from("parlay_house")], program.programId);
+  }
+
+  function getParlayVaultPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([Buffer.from("parlay_vault")], program.programId);
+  }
+
+  function getJurorVotePda(marketId: BN, juror: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("juror_vote"), marketId.toArrayLike(Buffer, "le", 8), juror.toBuffer()],
+      program.programId
+    );
+  }
+
+  // commitment = sha256(borsh(outcome) || salt || juror || market_id)
+  function voteCommitment(outcome: object, salt: Buffer, juror: PublicKey, marketId: BN): number[] {
+    const index = OUTCOME_INDEX[Object.keys(outcome)[0]];
+    return Array.from(
+      createHash("sha256")
+        .update(Buffer.from([index]))
+        .update(salt)
+        .update(juror.toBuffer())
+        .update(marketId.toArrayLike(Buffer, "le", 8))
+        .digest()
+    );
+  }
+
+  function evidenceFor(uri: string, document: string) {
+    return { uri, hash: Array.from(createHash("sha256").update(document).digest()) };
+  }
+
+  function getAttestationPda(marketId: BN, resolver: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("attestation"), marketId.toArrayLike(Buffer, "le", 8), resolver.toBuffer()],
+      program.programId
+    );
+  }
+
   function getMarketPda(marketId: BN): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
@@ -148,6 +322,37 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       1000_000_000 // 1000 USDC
     );
 
+    // Initialize protocol config (provider wallet is the upgrade authority)
+    console.log("⚙️  Initializing protocol config...");
+    const [configPda] = getConfigPda();
+    const [programDataPda] = getProgramDataPda();
+    await program.methods
+      .initializeConfig({
+        feeRecipient: creator.publicKey,
+        defaultFeeBps: 200, // 2%
+        maxFeeBps: 1_000, // 10%
+        creatorFeeShareBps: 2_000, // 20% of fees to market creators
+        minBet: new BN(1_000_000), // 1 USDC
+        maxBet: new BN(10_000_000_000), // 10,000 USDC
+        creationBond: new BN(50_000_000), // 50 USDC
+        resolutionBond: new BN(25_000_000), // 25 USDC
+        challengePeriod: new BN(5), // 5 seconds
+        minJurorStake: new BN(1_000_000), // 1 USDC
+        commitPeriod: new BN(5),
+        revealPeriod: new BN(5),
+        resolutionWindow: new BN(7 * 24 * 60 * 60), // 1 week
+        claimWindow: new BN(30 * 24 * 60 * 60), // 30 days
+      })
+      .accounts({
+        config: configPda,
+        collateralMint: usdcMint,
+        program: program.programId,
+        programData: programDataPda,
+        authority: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
     console.log("\n✅ Test environment setup complete!\n");
     console.log("USDC Mint:", usdcMint.toString());
     console.log("Creator:", creator.publicKey.toString());
@@ -167,9 +372,10 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       const description = "Resolves YES if BTC hits $100,000 or more before Dec 31, 2024";
       const endTime = new BN(Math.floor(Date.now() / 1000) + 60); // 1 minute from now
       const category = "Crypto";
+      const resolutionSource = "https://www.coingecko.com/en/coins/bitcoin";
 
       const tx = await program.methods
-        .createMarket(marketId, question, description, endTime, category)
+        .createMarket(marketId, question, description, endTime, category, null, MANUAL, resolutionSource, BINARY, null)
         .accounts({
           market: marketPda,
           vault: vaultPda,
@@ -190,6 +396,7 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       assert.equal(market.question, question);
       assert.equal(market.description, description);
       assert.equal(market.category, category);
+      assert.equal(market.resolutionSource, resolutionSource);
       assert.equal(market.id.toString(), marketId.toString());
       assert.equal(market.yesPool.toNumber(), 0);
       assert.equal(market.noPool.toNumber(), 0);
@@ -216,7 +423,12 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
             "Past market",
             "Should fail",
             pastEndTime,
-            "Test"
+            "Test",
+            null,
+            MANUAL,
+            RESOLUTION_SOURCE,
+            BINARY,
+            null
           )
           .accounts({
             market: pastMarketPda,
@@ -242,13 +454,13 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       console.log("\n💰 User1 betting YES with 100 USDC...");
 
       const amount = new BN(100_000_000); // 100 USDC
-      const prediction = true; // YES
+      const outcomeIndex = BET_YES;
 
       const [userStatsPda] = getUserStatsPda(user1.publicKey);
       const [betPda] = getBetPda(marketPda, user1.publicKey, new BN(0));
 
       const tx = await program.methods
-        .placeBet(marketId, amount, prediction)
+        .placeBet(marketId, amount, outcomeIndex)
         .accounts({
           market: marketPda,
           bet: betPda,
@@ -276,7 +488,7 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       const bet = await program.account.bet.fetch(betPda);
       assert.equal(bet.user.toString(), user1.publicKey.toString());
       assert.equal(bet.amount.toNumber(), 100_000_000);
-      assert.equal(bet.prediction, true);
+      assert.equal(bet.outcomeIndex, BET_YES);
       assert.equal(bet.claimed, false);
 
       // Verify user stats
@@ -291,13 +503,13 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       console.log("\n💰 User2 betting NO with 50 USDC...");
 
       const amount = new BN(50_000_000); // 50 USDC
-      const prediction = false; // NO
+      const outcomeIndex = BET_NO;
 
       const [userStatsPda] = getUserStatsPda(user2.publicKey);
       const [betPda] = getBetPda(marketPda, user2.publicKey, new BN(0));
 
       const tx = await program.methods
-        .placeBet(marketId, amount, prediction)
+        .placeBet(marketId, amount, outcomeIndex)
         .accounts({
           market: marketPda,
           bet: betPda,
@@ -328,13 +540,13 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       console.log("\n💰 User1 placing second bet YES with 25 USDC...");
 
       const amount = new BN(25_000_000); // 25 USDC
-      const prediction = true; // YES
+      const outcomeIndex = BET_YES;
 
       const [userStatsPda] = getUserStatsPda(user1.publicKey);
       const [betPda] = getBetPda(marketPda, user1.publicKey, new BN(1)); // Second bet
 
       const tx = await program.methods
-        .placeBet(marketId, amount, prediction)
+        .placeBet(marketId, amount, outcomeIndex)
         .accounts({
           market: marketPda,
           bet: betPda,
@@ -389,7 +601,7 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
 
       try {
         await program.methods
-          .placeBet(marketId, new BN(100_000_000), true)
+          .placeBet(marketId, new BN(100_000_000), BET_YES)
           .accounts({
             market: marketPda,
             bet: betPda,
@@ -412,9 +624,9 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
     });
   });
 
-  describe("3️⃣  Market Resolution (Creator-Only)", () => {
-    it("Should fail if non-creator tries to resolve", async () => {
-      console.log("\n❌ Testing non-creator resolution attempt...");
+  describe("3️⃣  Market Resolution (Resolver Role)", () => {
+    it("Should fail if a wallet without the resolver role tries to resolve", async () => {
+      console.log("\n❌ Testing unauthorized resolution attempt...");
 
       // Wait for market to end
       console.log("⏳ Waiting for market to end...");
@@ -422,38 +634,101 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
 
       try {
         await program.methods
-          .resolveMarket(marketId, true) // YES wins
+          .resolveMarket(marketId, YES, NO_EVIDENCE, null)
           .accounts({
             market: marketPda,
-            creator: user1.publicKey, // Wrong creator!
+            resolver: user1.publicKey, // No resolver role!
           })
           .signers([user1])
           .rpc();
 
         assert.fail("Should have thrown an error");
       } catch (err) {
-        console.log("✅ Correctly rejected non-creator resolution");
-        assert.include(err.toString(), "NotCreator");
+        console.log("✅ Correctly rejected unauthorized resolution");
+        assert.include(err.toString(), "UnauthorizedResolver");
       }
     });
 
-    it("Should allow creator to resolve market as YES", async () => {
-      console.log("\n🎯 Creator resolving market as YES...");
+    it("Should allow the admin to propose YES as the outcome", async () => {
+      console.log("\n🎯 Admin proposing YES...");
+
+      const evidence = evidenceFor(
+        "https://www.coingecko.com/en/coins/bitcoin/historical_data",
+        "BTC closed above $100,000 on 2024-12-05"
+      );
 
       const tx = await program.methods
-        .resolveMarket(marketId, true) // YES wins
+        .resolveMarket(marketId, YES, evidence, null)
         .accounts({
           market: marketPda,
-          creator: creator.publicKey,
+          resolver: creator.publicKey,
         })
         .rpc();
 
-      console.log("✅ Market resolved! TX:", tx.slice(0, 8) + "...");
+      console.log("✅ Resolution proposed! TX:", tx.slice(0, 8) + "...");
 
       // Verify market status
+      const market = await program.account.market.fetch(marketPda);
+      assert.isTrue("resolutionProposed" in market.status);
+      assert.deepEqual(market.outcome, YES); // YES proposed
+      assert.isFalse(market.isFinalized);
+      assert.isNotNull(market.challengeDeadline);
+      assert.equal(market.resolutionBond.toNumber(), 0);
+      assert.equal(market.evidence.uri, evidence.uri);
+      assert.deepEqual(Array.from(market.evidence.hash), evidence.hash);
+
+      console.log("✅ YES proposed, challenge window open");
+    });
+
+    it("Should not pay out before the resolution is finalized", async () => {
+      const [userStatsPda] = getUserStatsPda(user1.publicKey);
+      const [betPda] = getBetPda(marketPda, user1.publicKey, new BN(0));
+
+      try {
+        await program.methods
+          .claimWinnings(marketId)
+          .accounts({
+            market: marketPda,
+            bet: betPda,
+            userStats: userStatsPda,
+            vault: vaultPda,
+            userTokenAccount: user1TokenAccount,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to pay out during the challenge window");
+        assert.include(err.toString(), "ResolutionNotFinalized");
+      }
+    });
+
+    it("Should finalize the resolution after the challenge window", async () => {
+      try {
+        await program.methods
+          .finalizeResolution(marketId)
+          .accounts({ market: marketPda })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to finalize early");
+        assert.include(err.toString(), "ChallengePeriodActive");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+
+      await program.methods
+        .finalizeResolution(marketId)
+        .accounts({ market: marketPda })
+        .rpc();
+
       const market = await program.account.market.fetch(marketPda);
       assert.isTrue("resolved" in market.status);
-      assert.equal(market.outcome, true); // YES won
+      assert.deepEqual(market.outcome, YES); // YES won
       assert.isTrue(market.isFinalized);
       assert.isNotNull(market.resolutionTime);
 
@@ -468,10 +743,10 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
 
       try {
         await program.methods
-          .resolveMarket(marketId, false) // Try to change to NO
+          .resolveMarket(marketId, NO, NO_EVIDENCE, null) // Try to change to NO
           .accounts({
             market: marketPda,
-            creator: creator.publicKey,
+            resolver: creator.publicKey,
           })
           .rpc();
 
@@ -695,7 +970,12 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
           "Will ETH flip BTC?",
           "Resolves YES if ETH market cap > BTC market cap",
           endTime,
-          "Crypto"
+          "Crypto",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
         )
         .accounts({
           market: market2Pda,
@@ -716,7 +996,7 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       const [bet2Pda] = getBetPda(market2Pda, user2.publicKey, new BN(1)); // Second bet for user2
 
       await program.methods
-        .placeBet(market2Id, new BN(100_000_000), true)
+        .placeBet(market2Id, new BN(100_000_000), BET_YES)
         .accounts({
           market: market2Pda,
           bet: bet2Pda,
@@ -738,7 +1018,7 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       const [bet1Pda] = getBetPda(market2Pda, user1.publicKey, new BN(2)); // Third bet for user1
 
       await program.methods
-        .placeBet(market2Id, new BN(200_000_000), false)
+        .placeBet(market2Id, new BN(200_000_000), BET_NO)
         .accounts({
           market: market2Pda,
           bet: bet1Pda,
@@ -763,13 +1043,19 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
       // Resolve as NO
       console.log("🎯 Resolving market as NO...");
       await program.methods
-        .resolveMarket(market2Id, false) // NO wins
+        .resolveMarket(market2Id, NO, NO_EVIDENCE, null)
         .accounts({
           market: market2Pda,
-          creator: creator.publicKey,
+          resolver: creator.publicKey,
         })
         .rpc();
 
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(market2Id)
+        .accounts({ market: market2Pda })
+        .rpc();
+
       console.log("✅ Market resolved as NO!");
 
       // User1 claims (winner)
@@ -802,6 +1088,3860 @@ describe("Prediction Markets - Comprehensive Test Suite", () => {
     });
   });
 
+  describe("7️⃣  Protocol Config", () => {
+    it("Should store the admin and parameters in the config account", async () => {
+      const [configPda] = getConfigPda();
+      const config = await program.account.config.fetch(configPda);
+
+      assert.equal(config.admin.toString(), creator.publicKey.toString());
+      assert.equal(config.feeRecipient.toString(), creator.publicKey.toString());
+      assert.equal(config.collateralMint.toString(), usdcMint.toString());
+      assert.equal(config.defaultFeeBps, 200);
+      assert.equal(config.maxFeeBps, 1_000);
+      assert.equal(config.minBet.toNumber(), 1_000_000);
+      assert.equal(config.maxBet.toNumber(), 10_000_000_000);
+    });
+
+    it("Should fail if non-admin tries to update config", async () => {
+      const [configPda] = getConfigPda();
+
+      try {
+        await program.methods
+          .updateConfig({
+            feeRecipient: user1.publicKey,
+            defaultFeeBps: 0,
+            maxFeeBps: 0,
+            creatorFeeShareBps: 0,
+            minBet: new BN(1),
+            maxBet: new BN(10_000_000_000),
+            creationBond: new BN(0),
+            resolutionBond: new BN(0),
+            challengePeriod: new BN(1),
+            minJurorStake: new BN(0),
+            commitPeriod: new BN(1),
+            revealPeriod: new BN(1),
+            resolutionWindow: new BN(1),
+            claimWindow: new BN(1),
+          })
+          .accounts({
+            config: configPda,
+            admin: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected non-admin config update");
+        assert.include(err.toString(), "Unauthorized");
+      }
+    });
+
+    it("Should enforce bet limits from the config", async () => {
+      const [configPda] = getConfigPda();
+
+      await program.methods
+        .updateConfig({
+          feeRecipient: creator.publicKey,
+          defaultFeeBps: 200, // 2%
+          maxFeeBps: 1_000, // 10%
+          creatorFeeShareBps: 2_000,
+          minBet: new BN(5_000_000), // 5 USDC
+          maxBet: new BN(10_000_000_000),
+          creationBond: new BN(50_000_000),
+          resolutionBond: new BN(25_000_000),
+          challengePeriod: new BN(5),
+          minJurorStake: new BN(1_000_000),
+          commitPeriod: new BN(5),
+          revealPeriod: new BN(5),
+          resolutionWindow: new BN(7 * 24 * 60 * 60),
+          claimWindow: new BN(30 * 24 * 60 * 60),
+        })
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const limitMarketId = new BN(Date.now() + 2000);
+      const [limitMarketPda] = getMarketPda(limitMarketId);
+      const [limitVaultPda] = getVaultPda(limitMarketId);
+
+      await program.methods
+        .createMarket(
+          limitMarketId,
+          "Bet limit market",
+          "Used to test configured bet limits",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: limitMarketPda,
+          vault: limitVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const [userStatsPda] = getUserStatsPda(user1.publicKey);
+      const [betPda] = getBetPda(limitMarketPda, user1.publicKey, new BN(0));
+
+      try {
+        await program.methods
+          .placeBet(limitMarketId, new BN(2_000_000), BET_YES) // 2 USDC < 5 USDC minimum
+          .accounts({
+            market: limitMarketPda,
+            bet: betPda,
+            userStats: userStatsPda,
+            vault: limitVaultPda,
+            userTokenAccount: user1TokenAccount,
+            user: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected bet below configured minimum");
+        assert.include(err.toString(), "BetTooSmall");
+      }
+    });
+
+    it("Should fail to create a market with a non-collateral mint", async () => {
+      const otherMint = await createMint(
+        provider.connection,
+        creator.payer,
+        creator.publicKey,
+        null,
+        6
+      );
+
+      const badMarketId = new BN(Date.now() + 3000);
+      const [badMarketPda] = getMarketPda(badMarketId);
+      const [badVaultPda] = getVaultPda(badMarketId);
+
+      try {
+        await program.methods
+          .createMarket(
+            badMarketId,
+            "Wrong mint market",
+            "Should fail",
+            new BN(Math.floor(Date.now() / 1000) + 600),
+            "Test",
+            null,
+            MANUAL,
+            RESOLUTION_SOURCE,
+            BINARY,
+            null
+          )
+          .accounts({
+            market: badMarketPda,
+            vault: badVaultPda,
+            creator: creator.publicKey,
+            usdcMint: otherMint,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected non-collateral mint");
+        assert.include(err.toString(), "InvalidCollateralMint");
+      }
+    });
+  });
+
+  describe("8️⃣  Admin Transfer", () => {
+    it("Should fail if non-admin proposes a new admin", async () => {
+      const [configPda] = getConfigPda();
+
+      try {
+        await program.methods
+          .proposeAdmin(user1.publicKey)
+          .accounts({
+            config: configPda,
+            admin: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected non-admin proposal");
+        assert.include(err.toString(), "Unauthorized");
+      }
+    });
+
+    it("Should only let the pending admin accept", async () => {
+      const [configPda] = getConfigPda();
+
+      await program.methods
+        .proposeAdmin(user1.publicKey)
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      let config = await program.account.config.fetch(configPda);
+      assert.equal(config.pendingAdmin.toString(), user1.publicKey.toString());
+      assert.equal(config.admin.toString(), creator.publicKey.toString());
+
+      try {
+        await program.methods
+          .acceptAdmin()
+          .accounts({
+            config: configPda,
+            newAdmin: user2.publicKey,
+          })
+          .signers([user2])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected accept from wrong wallet");
+        assert.include(err.toString(), "NotPendingAdmin");
+      }
+    });
+
+    it("Should cancel a pending transfer", async () => {
+      const [configPda] = getConfigPda();
+
+      await program.methods
+        .cancelAdminTransfer()
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      assert.isNull(config.pendingAdmin);
+
+      try {
+        await program.methods
+          .acceptAdmin()
+          .accounts({
+            config: configPda,
+            newAdmin: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected accept after cancel");
+        assert.include(err.toString(), "NoPendingAdmin");
+      }
+    });
+
+    it("Should transfer admin rights once accepted", async () => {
+      const [configPda] = getConfigPda();
+
+      await program.methods
+        .proposeAdmin(user1.publicKey)
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          config: configPda,
+          newAdmin: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      let config = await program.account.config.fetch(configPda);
+      assert.equal(config.admin.toString(), user1.publicKey.toString());
+      assert.isNull(config.pendingAdmin);
+
+      // Hand admin rights back so later suites keep using the provider wallet
+      await program.methods
+        .proposeAdmin(creator.publicKey)
+        .accounts({
+          config: configPda,
+          admin: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          config: configPda,
+          newAdmin: creator.publicKey,
+        })
+        .rpc();
+
+      config = await program.account.config.fetch(configPda);
+      assert.equal(config.admin.toString(), creator.publicKey.toString());
+    });
+  });
+
+  describe("9️⃣  Multisig Admin", () => {
+    const multisigId = new BN(Date.now());
+    let multisigPda: PublicKey;
+    let authorityPda: PublicKey;
+
+    // Propose `ix` through the multisig, signed by `proposer`
+    async function proposeThroughMultisig(
+      ix: anchor.web3.TransactionInstruction,
+      proposer: Keypair | null
+    ): Promise<PublicKey> {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const [actionPda] = getMultisigActionPda(multisigPda, multisig.actionCount);
+
+      const builder = program.methods
+        .proposeAction(
+          ix.programId,
+          ix.keys.map((k) => ({ pubkey: k.pubkey, isSigner: k.isSigner, isWritable: k.isWritable })),
+          ix.data
+        )
+        .accounts({
+          multisig: multisigPda,
+          action: actionPda,
+          proposer: proposer ? proposer.publicKey : creator.publicKey,
+        });
+      await (proposer ? builder.signers([proposer]) : builder).rpc();
+
+      return actionPda;
+    }
+
+    async function executeThroughMultisig(
+      actionPda: PublicKey,
+      ix: anchor.web3.TransactionInstruction
+    ) {
+      await program.methods
+        .executeAction()
+        .accounts({
+          multisig: multisigPda,
+          action: actionPda,
+          authority: authorityPda,
+        })
+        .remainingAccounts([
+          ...ix.keys.map((k) => ({
+            pubkey: k.pubkey,
+            isSigner: false, // The multisig authority signs inside the CPI
+            isWritable: k.isWritable,
+          })),
+          { pubkey: ix.programId, isSigner: false, isWritable: false },
+        ])
+        .rpc();
+    }
+
+    it("Should create a 2-of-3 multisig", async () => {
+      [multisigPda] = getMultisigPda(multisigId);
+      [authorityPda] = getMultisigAuthorityPda(multisigPda);
+
+      await program.methods
+        .createMultisig(
+          multisigId,
+          [creator.publicKey, user1.publicKey, user2.publicKey],
+          2
+        )
+        .accounts({
+          multisig: multisigPda,
+          authority: authorityPda,
+          payer: creator.publicKey,
+        })
+        .rpc();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      assert.equal(multisig.signers.length, 3);
+      assert.equal(multisig.threshold, 2);
+    });
+
+    it("Should hand admin rights to the multisig only after enough approvals", async () => {
+      const [configPda] = getConfigPda();
+
+      await program.methods
+        .proposeAdmin(authorityPda)
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const acceptIx = await program.methods
+        .acceptAdmin()
+        .accounts({
+          config: configPda,
+          newAdmin: authorityPda,
+        })
+        .instruction();
+
+      const actionPda = await proposeThroughMultisig(acceptIx, null);
+
+      try {
+        await executeThroughMultisig(actionPda, acceptIx);
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected execution with 1 of 2 approvals");
+        assert.include(err.toString(), "NotEnoughApprovals");
+      }
+
+      await program.methods
+        .approveAction()
+        .accounts({
+          multisig: multisigPda,
+          action: actionPda,
+          approver: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      await executeThroughMultisig(actionPda, acceptIx);
+
+      const config = await program.account.config.fetch(configPda);
+      assert.equal(config.admin.toString(), authorityPda.toString());
+
+      const action = await program.account.multisigAction.fetch(actionPda);
+      assert.isTrue(action.executed);
+    });
+
+    it("Should create a market through the multisig", async () => {
+      // The authority PDA pays for the market and vault accounts it creates
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: creator.publicKey,
+            toPubkey: authorityPda,
+            lamports: 1 * anchor.web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
+
+      const marketId = new BN(Date.now());
+      const [marketPda] = getMarketPda(marketId);
+      const [vaultPda] = getVaultPda(marketId);
+      const endTime = new BN(Math.floor(Date.now() / 1000) + 86400);
+
+      const createIx = await program.methods
+        .createMarket(marketId, "Created by the multisig?", "Multisig market", endTime, "Governance", null, MANUAL, RESOLUTION_SOURCE, BINARY, null)
+        .accounts({
+          market: marketPda,
+          vault: vaultPda,
+          creator: authorityPda,
+          usdcMint: usdcMint,
+        })
+        .instruction();
+
+      const actionPda = await proposeThroughMultisig(createIx, null);
+      await program.methods
+        .approveAction()
+        .accounts({
+          multisig: multisigPda,
+          action: actionPda,
+          approver: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      await executeThroughMultisig(actionPda, createIx);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.creator.toString(), authorityPda.toString());
+      assert.equal(market.creationBond.toNumber(), 0);
+
+      try {
+        await executeThroughMultisig(actionPda, createIx);
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected executing the action twice");
+        assert.include(err.toString(), "ActionAlreadyExecuted");
+      }
+    });
+
+    it("Should reject approvals from non-signers", async () => {
+      const [configPda] = getConfigPda();
+      const outsider = Keypair.generate();
+
+      const updateIx = await program.methods
+        .proposeAdmin(creator.publicKey)
+        .accounts({
+          config: configPda,
+          admin: authorityPda,
+        })
+        .instruction();
+
+      const actionPda = await proposeThroughMultisig(updateIx, user2);
+
+      try {
+        await program.methods
+          .approveAction()
+          .accounts({
+            multisig: multisigPda,
+            action: actionPda,
+            approver: outsider.publicKey,
+          })
+          .signers([outsider])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected outsider approval");
+        assert.include(err.toString(), "NotMultisigSigner");
+      }
+
+      // Use this action to hand admin rights back to the provider wallet
+      await program.methods
+        .approveAction()
+        .accounts({
+          multisig: multisigPda,
+          action: actionPda,
+          approver: creator.publicKey,
+        })
+        .rpc();
+
+      await executeThroughMultisig(actionPda, updateIx);
+
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          config: configPda,
+          newAdmin: creator.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      assert.equal(config.admin.toString(), creator.publicKey.toString());
+    });
+  });
+
+  describe("🔟  Roles", () => {
+    it("Should let a granted market creator create markets until revoked", async () => {
+      const [configPda] = getConfigPda();
+      const [rolePda] = getRoleGrantPda(ROLE_MARKET_CREATOR, user1.publicKey);
+
+      await program.methods
+        .grantRole({ marketCreator: {} }, user1.publicKey)
+        .accounts({
+          config: configPda,
+          roleGrant: rolePda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const grant = await program.account.roleGrant.fetch(rolePda);
+      assert.equal(grant.wallet.toString(), user1.publicKey.toString());
+      assert.isTrue("marketCreator" in grant.role);
+
+      const curatedMarketId = new BN(Date.now() + 4000);
+      const [curatedMarketPda] = getMarketPda(curatedMarketId);
+      const [curatedVaultPda] = getVaultPda(curatedMarketId);
+
+      await program.methods
+        .createMarket(
+          curatedMarketId,
+          "Curated market",
+          "Created by a wallet holding the market creator role",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: curatedMarketPda,
+          vault: curatedVaultPda,
+          creator: user1.publicKey,
+          usdcMint: usdcMint,
+          creatorRole: rolePda,
+        })
+        .signers([user1])
+        .rpc();
+
+      const market = await program.account.market.fetch(curatedMarketPda);
+      assert.equal(market.creator.toString(), user1.publicKey.toString());
+
+      await program.methods
+        .revokeRole({ marketCreator: {} }, user1.publicKey)
+        .accounts({
+          config: configPda,
+          roleGrant: rolePda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const revokedMarketId = new BN(Date.now() + 5000);
+      const [revokedMarketPda] = getMarketPda(revokedMarketId);
+      const [revokedVaultPda] = getVaultPda(revokedMarketId);
+
+      try {
+        await program.methods
+          .createMarket(
+            revokedMarketId,
+            "Revoked market",
+            "Should fail",
+            new BN(Math.floor(Date.now() / 1000) + 600),
+            "Test",
+            null,
+            MANUAL,
+            RESOLUTION_SOURCE,
+            BINARY,
+            null
+          )
+          .accounts({
+            market: revokedMarketPda,
+            vault: revokedVaultPda,
+            creator: user1.publicKey,
+            usdcMint: usdcMint,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly required a creation bond after revoke");
+        assert.include(err.toString(), "MissingBondTokenAccount");
+      }
+    });
+
+    it("Should not let the resolver role cancel markets", async () => {
+      const [configPda] = getConfigPda();
+      const [resolverRolePda] = getRoleGrantPda(ROLE_RESOLVER, user2.publicKey);
+
+      await program.methods
+        .grantRole({ resolver: {} }, user2.publicKey)
+        .accounts({
+          config: configPda,
+          roleGrant: resolverRolePda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const cancelMarketId = new BN(Date.now() + 6000);
+      const [cancelMarketPda] = getMarketPda(cancelMarketId);
+      const [cancelVaultPda] = getVaultPda(cancelMarketId);
+
+      await program.methods
+        .createMarket(
+          cancelMarketId,
+          "Cancellable market",
+          "Only curators may cancel",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: cancelMarketPda,
+          vault: cancelVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .cancelMarket()
+          .accounts({
+            market: cancelMarketPda,
+            curator: user2.publicKey,
+          })
+          .signers([user2])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected cancel by resolver");
+        assert.include(err.toString(), "Unauthorized");
+      }
+
+      await program.methods
+        .cancelMarket()
+        .accounts({
+          market: cancelMarketPda,
+          curator: creator.publicKey,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(cancelMarketPda);
+      assert.isTrue("cancelled" in market.status);
+    });
+  });
+
+  describe("1️⃣1️⃣  Emergency Pause", () => {
+    let pauseMarketId: BN;
+    let pauseMarketPda: PublicKey;
+    let pauseVaultPda: PublicKey;
+
+    before(async () => {
+      pauseMarketId = new BN(Date.now() + 7000);
+      [pauseMarketPda] = getMarketPda(pauseMarketId);
+      [pauseVaultPda] = getVaultPda(pauseMarketId);
+
+      await program.methods
+        .createMarket(
+          pauseMarketId,
+          "Pause market",
+          "Used to test the circuit breaker",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: pauseMarketPda,
+          vault: pauseVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+    });
+
+    it("Should let a guardian pause betting", async () => {
+      const [configPda] = getConfigPda();
+      const [guardianRolePda] = getRoleGrantPda(ROLE_GUARDIAN, user2.publicKey);
+
+      await program.methods
+        .grantRole({ guardian: {} }, user2.publicKey)
+        .accounts({
+          config: configPda,
+          roleGrant: guardianRolePda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .pauseProtocol({ betting: {} })
+        .accounts({
+          config: configPda,
+          authority: user2.publicKey,
+          guardianRole: guardianRolePda,
+        })
+        .signers([user2])
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      assert.isTrue(config.bettingPaused);
+      assert.isFalse(config.paused);
+
+      const [userStatsPda] = getUserStatsPda(user1.publicKey);
+      const [betPda] = getBetPda(pauseMarketPda, user1.publicKey, new BN(0));
+
+      try {
+        await program.methods
+          .placeBet(pauseMarketId, new BN(10_000_000), BET_YES)
+          .accounts({
+            market: pauseMarketPda,
+            bet: betPda,
+            userStats: userStatsPda,
+            vault: pauseVaultPda,
+            userTokenAccount: user1TokenAccount,
+            user: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected bet while betting is paused");
+        assert.include(err.toString(), "ProtocolPaused");
+      }
+    });
+
+    it("Should fail if a wallet without the guardian role unpauses", async () => {
+      const [configPda] = getConfigPda();
+
+      try {
+        await program.methods
+          .unpauseProtocol({ betting: {} })
+          .accounts({
+            config: configPda,
+            authority: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected unauthorized unpause");
+        assert.include(err.toString(), "Unauthorized");
+      }
+    });
+
+    it("Should block market creation under a global pause", async () => {
+      const [configPda] = getConfigPda();
+      const [guardianRolePda] = getRoleGrantPda(ROLE_GUARDIAN, user2.publicKey);
+
+      await program.methods
+        .unpauseProtocol({ betting: {} })
+        .accounts({
+          config: configPda,
+          authority: user2.publicKey,
+          guardianRole: guardianRolePda,
+        })
+        .signers([user2])
+        .rpc();
+
+      await program.methods
+        .pauseProtocol({ all: {} })
+        .accounts({
+          config: configPda,
+          authority: creator.publicKey,
+        })
+        .rpc();
+
+      const blockedMarketId = new BN(Date.now() + 8000);
+      const [blockedMarketPda] = getMarketPda(blockedMarketId);
+      const [blockedVaultPda] = getVaultPda(blockedMarketId);
+
+      try {
+        await program.methods
+          .createMarket(
+            blockedMarketId,
+            "Blocked market",
+            "Should fail",
+            new BN(Math.floor(Date.now() / 1000) + 600),
+            "Test",
+            null,
+            MANUAL,
+            RESOLUTION_SOURCE,
+            BINARY,
+            null
+          )
+          .accounts({
+            market: blockedMarketPda,
+            vault: blockedVaultPda,
+            creator: creator.publicKey,
+            usdcMint: usdcMint,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected creation while paused");
+        assert.include(err.toString(), "ProtocolPaused");
+      }
+
+      try {
+        await program.methods
+          .voidMarket(pauseMarketId)
+          .accounts({
+            market: pauseMarketPda,
+            admin: creator.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected an admin void while paused");
+        assert.include(err.toString(), "ProtocolPaused");
+      }
+
+      await program.methods
+        .unpauseProtocol({ all: {} })
+        .accounts({
+          config: configPda,
+          authority: creator.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      assert.isFalse(config.paused);
+      assert.isFalse(config.bettingPaused);
+    });
+  });
+
+  describe("1️⃣2️⃣  Trading Halt", () => {
+    let haltMarketId: BN;
+    let haltMarketPda: PublicKey;
+    let haltVaultPda: PublicKey;
+
+    before(async () => {
+      haltMarketId = new BN(Date.now() + 9000);
+      [haltMarketPda] = getMarketPda(haltMarketId);
+      [haltVaultPda] = getVaultPda(haltMarketId);
+
+      await program.methods
+        .createMarket(
+          haltMarketId,
+          "Halt market",
+          "Used to test trading halts",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: haltMarketPda,
+          vault: haltVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+    });
+
+    it("Should fail if a wallet without the resolver role halts", async () => {
+      try {
+        await program.methods
+          .haltMarket(haltMarketId, 1)
+          .accounts({
+            market: haltMarketPda,
+            resolver: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected unauthorized halt");
+        assert.include(err.toString(), "UnauthorizedResolver");
+      }
+    });
+
+    it("Should reject bets while halted and accept them after resume", async () => {
+      // user2 was granted the resolver role in the roles suite
+      const [resolverRolePda] = getRoleGrantPda(ROLE_RESOLVER, user2.publicKey);
+
+      await program.methods
+        .haltMarket(haltMarketId, 7)
+        .accounts({
+          market: haltMarketPda,
+          resolver: user2.publicKey,
+          resolverRole: resolverRolePda,
+        })
+        .signers([user2])
+        .rpc();
+
+      let market = await program.account.market.fetch(haltMarketPda);
+      assert.isTrue("halted" in market.status);
+      assert.equal(market.haltReason, 7);
+      assert.isNotNull(market.haltedAt);
+
+      const [userStatsPda] = getUserStatsPda(user1.publicKey);
+      const [betPda] = getBetPda(haltMarketPda, user1.publicKey, new BN(0));
+      const betAccounts = {
+        market: haltMarketPda,
+        bet: betPda,
+        userStats: userStatsPda,
+        vault: haltVaultPda,
+        userTokenAccount: user1TokenAccount,
+        user: user1.publicKey,
+      };
+
+      try {
+        await program.methods
+          .placeBet(haltMarketId, new BN(10_000_000), BET_YES)
+          .accounts(betAccounts)
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected bet on halted market");
+        assert.include(err.toString(), "MarketHalted");
+      }
+
+      await program.methods
+        .resumeMarket(haltMarketId)
+        .accounts({
+          market: haltMarketPda,
+          resolver: user2.publicKey,
+          resolverRole: resolverRolePda,
+        })
+        .signers([user2])
+        .rpc();
+
+      market = await program.account.market.fetch(haltMarketPda);
+      assert.isTrue("active" in market.status);
+      assert.isNull(market.haltedAt);
+
+      await program.methods
+        .placeBet(haltMarketId, new BN(10_000_000), BET_YES)
+        .accounts(betAccounts)
+        .signers([user1])
+        .rpc();
+
+      market = await program.account.market.fetch(haltMarketPda);
+      assert.equal(market.totalBetsCount.toNumber(), 1);
+    });
+  });
+
+  describe("1️⃣3️⃣  Permissionless Creation Bond", () => {
+    it("Should escrow the bond and slash it when the market is cancelled", async () => {
+      const [configPda] = getConfigPda();
+      const [bondEscrowPda] = getBondEscrowPda();
+
+      const bondMarketId = new BN(Date.now() + 10000);
+      const [bondMarketPda] = getMarketPda(bondMarketId);
+      const [bondVaultPda] = getVaultPda(bondMarketId);
+
+      const balanceBefore = await getAccount(provider.connection, user1TokenAccount);
+
+      // user1 holds no market creator role, so the bond is required
+      await program.methods
+        .createMarket(
+          bondMarketId,
+          "Community market",
+          "Created permissionlessly with a bond",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Community",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: bondMarketPda,
+          vault: bondVaultPda,
+          bondEscrow: bondEscrowPda,
+          creator: user1.publicKey,
+          creatorTokenAccount: user1TokenAccount,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+
+      const balanceAfter = await getAccount(provider.connection, user1TokenAccount);
+      assert.equal(Number(balanceBefore.amount) - Number(balanceAfter.amount), 50_000_000);
+
+      let market = await program.account.market.fetch(bondMarketPda);
+      assert.equal(market.creationBond.toNumber(), 50_000_000);
+
+      // Bond cannot be settled while the market is live
+      try {
+        await program.methods
+          .settleCreationBond(bondMarketId)
+          .accounts({
+            market: bondMarketPda,
+            bondEscrow: bondEscrowPda,
+            destination: user1TokenAccount,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to settle a live market's bond");
+        assert.include(err.toString(), "MarketNotResolved");
+      }
+
+      // Admin cancels it as spam, the bond goes to the fee recipient
+      await program.methods
+        .cancelMarket()
+        .accounts({
+          market: bondMarketPda,
+          curator: creator.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      assert.equal(config.feeRecipient.toString(), creator.publicKey.toString());
+
+      const recipientBefore = await getAccount(provider.connection, creatorTokenAccount);
+
+      await program.methods
+        .settleCreationBond(bondMarketId)
+        .accounts({
+          market: bondMarketPda,
+          bondEscrow: bondEscrowPda,
+          destination: creatorTokenAccount,
+        })
+        .rpc();
+
+      const recipientAfter = await getAccount(provider.connection, creatorTokenAccount);
+      assert.equal(Number(recipientAfter.amount) - Number(recipientBefore.amount), 50_000_000);
+
+      market = await program.account.market.fetch(bondMarketPda);
+      assert.equal(market.creationBond.toNumber(), 0);
+    });
+
+    it("Should slash the bond of a market voided after bets were placed", async () => {
+      const [bondEscrowPda] = getBondEscrowPda();
+      const bondMarketId = new BN(Date.now() + 10001);
+      const [bondMarketPda] = getMarketPda(bondMarketId);
+      const [bondVaultPda] = getVaultPda(bondMarketId);
+
+      await program.methods
+        .createMarket(
+          bondMarketId,
+          "Ambiguous community market",
+          "Created permissionlessly with a bond",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Community",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: bondMarketPda,
+          vault: bondVaultPda,
+          bondEscrow: bondEscrowPda,
+          creator: user1.publicKey,
+          creatorTokenAccount: user1TokenAccount,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+
+      const [user2StatsPda] = getUserStatsPda(user2.publicKey);
+      const [betPda] = getBetPda(bondMarketPda, user2.publicKey, new BN(0));
+      await program.methods
+        .placeBet(bondMarketId, new BN(10_000_000), BET_YES)
+        .accounts({
+          market: bondMarketPda,
+          bet: betPda,
+          userStats: user2StatsPda,
+          vault: bondVaultPda,
+          userTokenAccount: user2TokenAccount,
+          user: user2.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user2])
+        .rpc();
+
+      try {
+        await program.methods
+          .cancelMarket()
+          .accounts({
+            market: bondMarketPda,
+            curator: creator.publicKey,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        assert.include(err.toString(), "HasBets");
+      }
+
+      await program.methods
+        .voidMarket(bondMarketId)
+        .accounts({
+          market: bondMarketPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(bondMarketPda);
+      assert.deepEqual(market.outcome, INVALID);
+
+      try {
+        await program.methods
+          .settleCreationBond(bondMarketId)
+          .accounts({
+            market: bondMarketPda,
+            bondEscrow: bondEscrowPda,
+            destination: user1TokenAccount,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to return the bond of a voided market");
+        assert.include(err.toString(), "InvalidBondDestination");
+      }
+
+      const recipientBefore = await getAccount(provider.connection, creatorTokenAccount);
+      await program.methods
+        .settleCreationBond(bondMarketId)
+        .accounts({
+          market: bondMarketPda,
+          bondEscrow: bondEscrowPda,
+          destination: creatorTokenAccount,
+        })
+        .rpc();
+      const recipientAfter = await getAccount(provider.connection, creatorTokenAccount);
+      assert.equal(Number(recipientAfter.amount) - Number(recipientBefore.amount), 50_000_000);
+    });
+  });
+
+  describe("1️⃣4️⃣  Market Proposals", () => {
+    it("Should materialise a market when a curator approves a proposal", async () => {
+      const proposalId = new BN(Date.now());
+      const [proposalPda] = getProposalPda(proposalId);
+      const endTime = new BN(Math.floor(Date.now() / 1000) + 3600);
+
+      await program.methods
+        .proposeMarket(
+          proposalId,
+          "Will the community pick this question?",
+          "Suggested by a user, tradeable once approved",
+          endTime,
+          "Community",
+          RESOLUTION_SOURCE
+        )
+        .accounts({
+          proposal: proposalPda,
+          proposer: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      let proposal = await program.account.marketProposal.fetch(proposalPda);
+      assert.isTrue("pending" in proposal.status);
+      assert.equal(proposal.proposer.toString(), user2.publicKey.toString());
+
+      const proposedMarketId = new BN(Date.now() + 11000);
+      const [proposedMarketPda] = getMarketPda(proposedMarketId);
+      const [proposedVaultPda] = getVaultPda(proposedMarketId);
+
+      try {
+        await program.methods
+          .approveProposal(proposalId, proposedMarketId)
+          .accounts({
+            proposal: proposalPda,
+            market: proposedMarketPda,
+            vault: proposedVaultPda,
+            usdcMint: usdcMint,
+            curator: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected approval by non-curator");
+        assert.include(err.toString(), "Unauthorized");
+      }
+
+      await program.methods
+        .approveProposal(proposalId, proposedMarketId)
+        .accounts({
+          proposal: proposalPda,
+          market: proposedMarketPda,
+          vault: proposedVaultPda,
+          usdcMint: usdcMint,
+          curator: creator.publicKey,
+        })
+        .rpc();
+
+      proposal = await program.account.marketProposal.fetch(proposalPda);
+      assert.isTrue("approved" in proposal.status);
+      assert.equal(proposal.market.toString(), proposedMarketPda.toString());
+
+      const market = await program.account.market.fetch(proposedMarketPda);
+      assert.equal(market.creator.toString(), user2.publicKey.toString());
+      assert.equal(market.endTime.toString(), endTime.toString());
+      assert.equal(market.vault.toString(), proposedVaultPda.toString());
+      assert.isTrue("active" in market.status);
+    });
+
+    it("Should not approve a rejected proposal", async () => {
+      const proposalId = new BN(Date.now() + 1);
+      const [proposalPda] = getProposalPda(proposalId);
+
+      await program.methods
+        .proposeMarket(
+          proposalId,
+          "Ambiguous question?",
+          "Will be rejected",
+          new BN(Math.floor(Date.now() / 1000) + 3600),
+          "Community",
+          RESOLUTION_SOURCE
+        )
+        .accounts({
+          proposal: proposalPda,
+          proposer: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      await program.methods
+        .rejectProposal(proposalId)
+        .accounts({
+          proposal: proposalPda,
+          curator: creator.publicKey,
+        })
+        .rpc();
+
+      const proposal = await program.account.marketProposal.fetch(proposalPda);
+      assert.isTrue("rejected" in proposal.status);
+
+      const rejectedMarketId = new BN(Date.now() + 12000);
+      const [rejectedMarketPda] = getMarketPda(rejectedMarketId);
+      const [rejectedVaultPda] = getVaultPda(rejectedMarketId);
+
+      try {
+        await program.methods
+          .approveProposal(proposalId, rejectedMarketId)
+          .accounts({
+            proposal: proposalPda,
+            market: rejectedMarketPda,
+            vault: rejectedVaultPda,
+            usdcMint: usdcMint,
+            curator: creator.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected approval of a rejected proposal");
+        assert.include(err.toString(), "ProposalNotPending");
+      }
+    });
+  });
+
+  describe("1️⃣5️⃣  Market Fees", () => {
+    it("Should store a creator-chosen fee in basis points", async () => {
+      const feeMarketId = new BN(Date.now() + 13000);
+      const [feeMarketPda] = getMarketPda(feeMarketId);
+      const [feeVaultPda] = getVaultPda(feeMarketId);
+
+      await program.methods
+        .createMarket(
+          feeMarketId,
+          "Half percent fee market",
+          "Charges 0.5% on winning payouts",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Test",
+          50, // 0.5%
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: feeMarketPda,
+          vault: feeVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(feeMarketPda);
+      assert.equal(market.feeBps, 50);
+    });
+
+    it("Should use the protocol default fee when none is given", async () => {
+      const defaultMarketId = new BN(Date.now() + 14000);
+      const [defaultMarketPda] = getMarketPda(defaultMarketId);
+      const [defaultVaultPda] = getVaultPda(defaultMarketId);
+
+      await program.methods
+        .createMarket(
+          defaultMarketId,
+          "Default fee market",
+          "Uses the protocol default fee",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: defaultMarketPda,
+          vault: defaultVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(defaultMarketPda);
+      assert.equal(market.feeBps, 200);
+    });
+
+    it("Should fail to create a market above the protocol max fee", async () => {
+      const highFeeMarketId = new BN(Date.now() + 15000);
+      const [highFeeMarketPda] = getMarketPda(highFeeMarketId);
+      const [highFeeVaultPda] = getVaultPda(highFeeMarketId);
+
+      try {
+        await program.methods
+          .createMarket(
+            highFeeMarketId,
+            "Greedy market",
+            "Should fail",
+            new BN(Math.floor(Date.now() / 1000) + 600),
+            "Test",
+            1_500, // 15% > 10% max
+            MANUAL,
+            RESOLUTION_SOURCE,
+            BINARY,
+            null
+          )
+          .accounts({
+            market: highFeeMarketPda,
+            vault: highFeeVaultPda,
+            creator: creator.publicKey,
+            usdcMint: usdcMint,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected fee above protocol max");
+        assert.include(err.toString(), "InvalidFee");
+      }
+    });
+  });
+
+  describe("1️⃣6️⃣  Creator Revenue Share", () => {
+    it("Should split claimed fees between creator and protocol", async () => {
+      // Market 1 was resolved YES and user1 claimed two winning bets
+      const market = await program.account.market.fetch(marketPda);
+      const creatorFees = market.creatorFeesAccrued.toNumber();
+      const protocolFees = market.protocolFeesAccrued.toNumber();
+
+      assert.isTrue(creatorFees > 0);
+      assert.isTrue(protocolFees > 0);
+      assert.equal(market.creatorFeeShareBps, 2_000);
+      // 20% / 80% split, allowing for per-claim rounding
+      assert.approximately(creatorFees * 4, protocolFees, 8);
+    });
+
+    it("Should fail if a non-creator claims creator fees", async () => {
+      try {
+        await program.methods
+          .claimCreatorFees(marketId)
+          .accounts({
+            market: marketPda,
+            vault: vaultPda,
+            creatorTokenAccount: user1TokenAccount,
+            creator: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected non-creator fee claim");
+        assert.include(err.toString(), "NotCreator");
+      }
+    });
+
+    it("Should pay the creator and the treasury their exact shares", async () => {
+      const market = await program.account.market.fetch(marketPda);
+
+      const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+      await program.methods
+        .claimCreatorFees(marketId)
+        .accounts({
+          market: marketPda,
+          vault: vaultPda,
+          creatorTokenAccount: creatorTokenAccount,
+          creator: creator.publicKey,
+        })
+        .rpc();
+      const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+      assert.equal(
+        Number(creatorAfter.amount) - Number(creatorBefore.amount),
+        market.creatorFeesAccrued.toNumber()
+      );
+
+      // Fee recipient is the provider wallet as well
+      await program.methods
+        .withdrawFees(marketId)
+        .accounts({
+          market: marketPda,
+          vault: vaultPda,
+          feeRecipientTokenAccount: creatorTokenAccount,
+          treasurer: creator.publicKey,
+        })
+        .rpc();
+      const treasuryAfter = await getAccount(provider.connection, creatorTokenAccount);
+      assert.equal(
+        Number(treasuryAfter.amount) - Number(creatorAfter.amount),
+        market.protocolFeesAccrued.toNumber()
+      );
+
+      try {
+        await program.methods
+          .withdrawFees(marketId)
+          .accounts({
+            market: marketPda,
+            vault: vaultPda,
+            feeRecipientTokenAccount: creatorTokenAccount,
+            treasurer: creator.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a second withdrawal");
+        assert.include(err.toString(), "NoFeesToWithdraw");
+      }
+    });
+
+    it("Should sweep the residual once every bet is claimed", async () => {
+      const sweepMarketId = new BN(Date.now() + 16000);
+      const [sweepMarketPda] = getMarketPda(sweepMarketId);
+      const [sweepVaultPda] = getVaultPda(sweepMarketId);
+
+      await program.methods
+        .createMarket(
+          sweepMarketId,
+          "Lopsided market",
+          "Heavier losing side leaves a surplus",
+          new BN(Math.floor(Date.now() / 1000) + 4),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: sweepMarketPda,
+          vault: sweepVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const [user1StatsPda] = getUserStatsPda(user1.publicKey);
+      const [user2StatsPda] = getUserStatsPda(user2.publicKey);
+      const [winningBetPda] = getBetPda(sweepMarketPda, user1.publicKey, new BN(0));
+      const [losingBetPda] = getBetPda(sweepMarketPda, user2.publicKey, new BN(1));
+      await program.methods
+        .placeBet(sweepMarketId, new BN(10_000_000), BET_YES)
+        .accounts({
+          market: sweepMarketPda,
+          bet: winningBetPda,
+          userStats: user1StatsPda,
+          vault: sweepVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .placeBet(sweepMarketId, new BN(30_000_000), BET_NO)
+        .accounts({
+          market: sweepMarketPda,
+          bet: losingBetPda,
+          userStats: user2StatsPda,
+          vault: sweepVaultPda,
+          userTokenAccount: user2TokenAccount,
+          user: user2.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user2])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+      await program.methods
+        .resolveMarket(sweepMarketId, YES, NO_EVIDENCE, null)
+        .accounts({
+          market: sweepMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(sweepMarketId)
+        .accounts({ market: sweepMarketPda })
+        .rpc();
+
+      await program.methods
+        .claimWinnings(sweepMarketId)
+        .accounts({
+          market: sweepMarketPda,
+          bet: winningBetPda,
+          userStats: user1StatsPda,
+          vault: sweepVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      const sweep = () =>
+        program.methods
+          .sweepResidual(sweepMarketId)
+          .accounts({
+            market: sweepMarketPda,
+            vault: sweepVaultPda,
+            feeRecipientTokenAccount: creatorTokenAccount,
+            treasurer: creator.publicKey,
+          })
+          .rpc();
+
+      try {
+        await sweep();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to sweep while a bet is unclaimed");
+        assert.include(err.toString(), "ClaimWindowOpen");
+      }
+
+      await program.methods
+        .claimWinnings(sweepMarketId)
+        .accounts({
+          market: sweepMarketPda,
+          bet: losingBetPda,
+          userStats: user2StatsPda,
+          vault: sweepVaultPda,
+          userTokenAccount: user2TokenAccount,
+          user: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      await sweep();
+
+      const market = await program.account.market.fetch(sweepMarketPda);
+      assert.isTrue(market.residualSwept);
+      const vault = await getAccount(provider.connection, sweepVaultPda);
+      // Only the creator's unclaimed fee share is left behind
+      assert.equal(Number(vault.amount), market.creatorFeesAccrued.toNumber());
+    });
+  });
+
+  describe("1️⃣7️⃣  Optimistic Resolution", () => {
+    it("Should let jurors settle a challenged resolution", async () => {
+      const [bondEscrowPda] = getBondEscrowPda();
+
+      const disputeMarketId = new BN(Date.now() + 30000);
+      const [disputeMarketPda] = getMarketPda(disputeMarketId);
+      const [disputeVaultPda] = getVaultPda(disputeMarketId);
+      const [disputePda] = getDisputePda(disputeMarketId);
+      const [jurorVaultPda] = getDisputeVaultPda(disputeMarketId);
+
+      // Juror with its own funds; the provider wallet votes as well
+      const juror = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        juror.publicKey,
+        1 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+      const jurorTokenAccount = await createAccount(
+        provider.connection,
+        juror,
+        usdcMint,
+        juror.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        creator.payer,
+        usdcMint,
+        jurorTokenAccount,
+        creator.publicKey,
+        100_000_000
+      );
+
+      await program.methods
+        .createMarket(
+          disputeMarketId,
+          "Disputed market",
+          "Resolved through a challenge",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: disputeMarketPda,
+          vault: disputeVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      // user1 proposes YES with a bond
+      const user1Before = await getAccount(provider.connection, user1TokenAccount);
+      await program.methods
+        .proposeResolution(disputeMarketId, YES, NO_EVIDENCE)
+        .accounts({
+          market: disputeMarketPda,
+          usdcMint: usdcMint,
+          bondEscrow: bondEscrowPda,
+          proposerTokenAccount: user1TokenAccount,
+          proposer: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+      const user1After = await getAccount(provider.connection, user1TokenAccount);
+      assert.equal(Number(user1Before.amount) - Number(user1After.amount), 25_000_000);
+
+      // user2 disagrees and posts the counter-bond, opening the juror vote
+      const user2Before = await getAccount(provider.connection, user2TokenAccount);
+      await program.methods
+        .challengeResolution(disputeMarketId, NO)
+        .accounts({
+          market: disputeMarketPda,
+          usdcMint: usdcMint,
+          bondEscrow: bondEscrowPda,
+          dispute: disputePda,
+          disputeVault: jurorVaultPda,
+          challengerTokenAccount: user2TokenAccount,
+          challenger: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      let market = await program.account.market.fetch(disputeMarketPda);
+      assert.isTrue("disputed" in market.status);
+      assert.equal(market.challenger.toString(), user2.publicKey.toString());
+      assert.equal(market.challengeBond.toNumber(), 25_000_000);
+
+      // Bond holders cannot sit on their own jury
+      try {
+        await program.methods
+          .commitVote(disputeMarketId, voteCommitment(YES, randomBytes(32), user1.publicKey, disputeMarketId), new BN(5_000_000))
+          .accounts({
+            market: disputeMarketPda,
+            dispute: disputePda,
+            disputeVault: jurorVaultPda,
+            jurorVote: getJurorVotePda(disputeMarketId, user1.publicKey)[0],
+            jurorTokenAccount: user1TokenAccount,
+            juror: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a conflicted juror");
+        assert.include(err.toString(), "ConflictedJuror");
+      }
+
+      // juror stakes 10 USDC on NO, the provider wallet 5 USDC on YES
+      const jurorSalt = randomBytes(32);
+      const creatorSalt = randomBytes(32);
+      const [jurorVotePda] = getJurorVotePda(disputeMarketId, juror.publicKey);
+      const [creatorVotePda] = getJurorVotePda(disputeMarketId, creator.publicKey);
+
+      await program.methods
+        .commitVote(disputeMarketId, voteCommitment(NO, jurorSalt, juror.publicKey, disputeMarketId), new BN(10_000_000))
+        .accounts({
+          market: disputeMarketPda,
+          dispute: disputePda,
+          disputeVault: jurorVaultPda,
+          jurorVote: jurorVotePda,
+          jurorTokenAccount: jurorTokenAccount,
+          juror: juror.publicKey,
+        })
+        .signers([juror])
+        .rpc();
+      await program.methods
+        .commitVote(disputeMarketId, voteCommitment(YES, creatorSalt, creator.publicKey, disputeMarketId), new BN(5_000_000))
+        .accounts({
+          market: disputeMarketPda,
+          dispute: disputePda,
+          disputeVault: jurorVaultPda,
+          jurorVote: creatorVotePda,
+          jurorTokenAccount: creatorTokenAccount,
+          juror: creator.publicKey,
+        })
+        .rpc();
+
+      // A disputed market cannot be finalized optimistically
+      try {
+        await program.methods
+          .finalizeResolution(disputeMarketId)
+          .accounts({ market: disputeMarketPda })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to finalize a disputed market");
+        assert.include(err.toString(), "NoResolutionProposed");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+
+      // A reveal has to match the commitment
+      try {
+        await program.methods
+          .revealVote(disputeMarketId, YES, Array.from(jurorSalt))
+          .accounts({
+            dispute: disputePda,
+            jurorVote: jurorVotePda,
+            juror: juror.publicKey,
+          })
+          .signers([juror])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a mismatched reveal");
+        assert.include(err.toString(), "InvalidReveal");
+      }
+
+      await program.methods
+        .revealVote(disputeMarketId, NO, Array.from(jurorSalt))
+        .accounts({
+          dispute: disputePda,
+          jurorVote: jurorVotePda,
+          juror: juror.publicKey,
+        })
+        .signers([juror])
+        .rpc();
+      await program.methods
+        .revealVote(disputeMarketId, YES, Array.from(creatorSalt))
+        .accounts({
+          dispute: disputePda,
+          jurorVote: creatorVotePda,
+          juror: creator.publicKey,
+        })
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+
+      // NO has the stake-weighted majority, so the challenger wins
+      await program.methods
+        .settleDispute(disputeMarketId)
+        .accounts({
+          market: disputeMarketPda,
+          dispute: disputePda,
+          disputeVault: jurorVaultPda,
+          bondEscrow: bondEscrowPda,
+          winnerTokenAccount: user2TokenAccount,
+        })
+        .rpc();
+
+      const user2After = await getAccount(provider.connection, user2TokenAccount);
+      assert.equal(Number(user2After.amount) - Number(user2Before.amount), 0); // Bond returned
+
+      market = await program.account.market.fetch(disputeMarketPda);
+      assert.isTrue("resolved" in market.status);
+      assert.deepEqual(market.outcome, NO);
+      assert.isTrue(market.isFinalized);
+      assert.equal(market.resolutionBond.toNumber(), 0);
+      assert.equal(market.challengeBond.toNumber(), 0);
+
+      const dispute = await program.account.dispute.fetch(disputePda);
+      assert.deepEqual(dispute.winningOutcome, NO);
+      assert.equal(dispute.rewardPool.toNumber(), 30_000_000); // Proposer bond + YES stake
+
+      // The minority juror gets nothing back
+      try {
+        await program.methods
+          .claimJurorReward(disputeMarketId)
+          .accounts({
+            dispute: disputePda,
+            disputeVault: jurorVaultPda,
+            jurorVote: creatorVotePda,
+            jurorTokenAccount: creatorTokenAccount,
+            juror: creator.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a minority juror claim");
+        assert.include(err.toString(), "NotWinningJuror");
+      }
+
+      const jurorBefore = await getAccount(provider.connection, jurorTokenAccount);
+      await program.methods
+        .claimJurorReward(disputeMarketId)
+        .accounts({
+          dispute: disputePda,
+          disputeVault: jurorVaultPda,
+          jurorVote: jurorVotePda,
+          jurorTokenAccount: jurorTokenAccount,
+          juror: juror.publicKey,
+        })
+        .signers([juror])
+        .rpc();
+      const jurorAfter = await getAccount(provider.connection, jurorTokenAccount);
+      assert.equal(Number(jurorAfter.amount) - Number(jurorBefore.amount), 40_000_000);
+    });
+  });
+
+  describe("1️⃣8️⃣  Invalid Outcome", () => {
+    it("Should refund every bet in full when a market resolves Invalid", async () => {
+      const voidMarketId = new BN(Date.now() + 40000);
+      const [voidMarketPda] = getMarketPda(voidMarketId);
+      const [voidVaultPda] = getVaultPda(voidMarketId);
+
+      await program.methods
+        .createMarket(
+          voidMarketId,
+          "Will the match be won at home?",
+          "Voided if the match is postponed",
+          new BN(Math.floor(Date.now() / 1000) + 5),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: voidMarketPda,
+          vault: voidVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const [user1StatsPda] = getUserStatsPda(user1.publicKey);
+      const [user2StatsPda] = getUserStatsPda(user2.publicKey);
+      const [bet1Pda] = getBetPda(voidMarketPda, user1.publicKey, new BN(0));
+      const [bet2Pda] = getBetPda(voidMarketPda, user2.publicKey, new BN(1));
+
+      await program.methods
+        .placeBet(voidMarketId, new BN(10_000_000), BET_YES)
+        .accounts({
+          market: voidMarketPda,
+          bet: bet1Pda,
+          userStats: user1StatsPda,
+          vault: voidVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .placeBet(voidMarketId, new BN(20_000_000), BET_NO)
+        .accounts({
+          market: voidMarketPda,
+          bet: bet2Pda,
+          userStats: user2StatsPda,
+          vault: voidVaultPda,
+          userTokenAccount: user2TokenAccount,
+          user: user2.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user2])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+
+      await program.methods
+        .resolveMarket(voidMarketId, INVALID, NO_EVIDENCE, null)
+        .accounts({
+          market: voidMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(voidMarketId)
+        .accounts({ market: voidMarketPda })
+        .rpc();
+
+      const market = await program.account.market.fetch(voidMarketPda);
+      assert.deepEqual(market.outcome, INVALID);
+
+      const user1StatsBefore = await program.account.userStats.fetch(user1StatsPda);
+      const user2StatsBefore = await program.account.userStats.fetch(user2StatsPda);
+
+      // A bet from another market cannot be refunded out of this vault
+      try {
+        await program.methods
+          .claimWinnings(voidMarketId)
+          .accounts({
+            market: voidMarketPda,
+            bet: getBetPda(marketPda, user1.publicKey, new BN(0))[0],
+            userStats: user1StatsPda,
+            vault: voidVaultPda,
+            userTokenAccount: user1TokenAccount,
+            user: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a bet from another market");
+        assert.include(err.toString(), "BetMarketMismatch");
+      }
+
+      const claims = [
+        { user: user1, bet: bet1Pda, stats: user1StatsPda, tokenAccount: user1TokenAccount, amount: 10_000_000 },
+        { user: user2, bet: bet2Pda, stats: user2StatsPda, tokenAccount: user2TokenAccount, amount: 20_000_000 },
+      ];
+      for (const claim of claims) {
+        const before = await getAccount(provider.connection, claim.tokenAccount);
+        await program.methods
+          .claimWinnings(voidMarketId)
+          .accounts({
+            market: voidMarketPda,
+            bet: claim.bet,
+            userStats: claim.stats,
+            vault: voidVaultPda,
+            userTokenAccount: claim.tokenAccount,
+            user: claim.user.publicKey,
+          })
+          .signers([claim.user])
+          .rpc();
+        const after = await getAccount(provider.connection, claim.tokenAccount);
+        assert.equal(Number(after.amount) - Number(before.amount), claim.amount);
+      }
+
+      // No fee and no win or loss recorded
+      const marketAfter = await program.account.market.fetch(voidMarketPda);
+      assert.equal(marketAfter.creatorFeesAccrued.toNumber(), 0);
+      assert.equal(marketAfter.protocolFeesAccrued.toNumber(), 0);
+
+      const user1StatsAfter = await program.account.userStats.fetch(user1StatsPda);
+      const user2StatsAfter = await program.account.userStats.fetch(user2StatsPda);
+      assert.equal(user1StatsAfter.winCount.toNumber(), user1StatsBefore.winCount.toNumber());
+      assert.equal(user1StatsAfter.lossCount.toNumber(), user1StatsBefore.lossCount.toNumber());
+      assert.equal(user2StatsAfter.winCount.toNumber(), user2StatsBefore.winCount.toNumber());
+      assert.equal(user2StatsAfter.lossCount.toNumber(), user2StatsBefore.lossCount.toNumber());
+    });
+  });
+
+  describe("1️⃣9️⃣  Oracle Markets", () => {
+    it("Should resolve a price market from the Pyth feed", async () => {
+      const priceMarketId = new BN(Date.now() + 50000);
+      const [priceMarketPda] = getMarketPda(priceMarketId);
+      const [priceVaultPda] = getVaultPda(priceMarketId);
+
+      // YES if SOL/USD > $150
+      const condition = {
+        source: { pyth: {} },
+        feed: PYTH_SOL_USD_FEED,
+        feedId: SOL_USD_FEED_ID,
+        threshold: new BN(150),
+        thresholdExpo: 0,
+        comparator: { above: {} },
+        maxStaleness: FIXTURE_PUBLISH_WINDOW,
+        maxConfidenceBps: 100,
+      };
+
+      await program.methods
+        .createMarket(
+          priceMarketId,
+          "Will SOL be above $150?",
+          "Resolved from the Pyth SOL/USD feed",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Crypto",
+          null,
+          { priceFeed: { 0: condition } },
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: priceMarketPda,
+          vault: priceVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .resolveWithOracle(priceMarketId)
+          .accounts({ market: priceMarketPda, priceFeed: PYTH_SOL_USD_FEED })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly refused to resolve before the end time");
+        assert.include(err.toString(), "MarketNotEnded");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      // Oracle markets cannot be resolved by hand
+      try {
+        await program.methods
+          .resolveMarket(priceMarketId, NO, NO_EVIDENCE, null)
+          .accounts({
+            market: priceMarketPda,
+            resolver: creator.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected manual resolution");
+        assert.include(err.toString(), "WrongResolutionMode");
+      }
+
+      try {
+        await program.methods
+          .resolveWithOracle(priceMarketId)
+          .accounts({ market: priceMarketPda, priceFeed: usdcMint })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a different feed account");
+        assert.include(err.toString(), "InvalidPriceFeed");
+      }
+
+      await program.methods
+        .resolveWithOracle(priceMarketId)
+        .accounts({ market: priceMarketPda, priceFeed: PYTH_SOL_USD_FEED })
+        .rpc();
+
+      const market = await program.account.market.fetch(priceMarketPda);
+      assert.isTrue("resolved" in market.status);
+      assert.isTrue(market.isFinalized);
+      assert.deepEqual(market.outcome, YES); // $150.25 > $150
+      assert.equal(market.resolver.toString(), PYTH_SOL_USD_FEED.toString());
+    });
+
+    it("Should reject a Pyth update for a different feed id", async () => {
+      const priceMarketId = new BN(Date.now() + 50500);
+      const [priceMarketPda] = getMarketPda(priceMarketId);
+      const [priceVaultPda] = getVaultPda(priceMarketId);
+
+      // Same account, but the market expects another feed to be posted into it
+      const condition = {
+        source: { pyth: {} },
+        feed: PYTH_SOL_USD_FEED,
+        feedId: new Array(32).fill(1),
+        threshold: new BN(150),
+        thresholdExpo: 0,
+        comparator: { above: {} },
+        maxStaleness: FIXTURE_PUBLISH_WINDOW,
+        maxConfidenceBps: 100,
+      };
+
+      await program.methods
+        .createMarket(
+          priceMarketId,
+          "Will ETH be above $150?",
+          "Resolved from a Pyth feed",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Crypto",
+          null,
+          { priceFeed: { 0: condition } },
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: priceMarketPda,
+          vault: priceVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      try {
+        await program.methods
+          .resolveWithOracle(priceMarketId)
+          .accounts({ market: priceMarketPda, priceFeed: PYTH_SOL_USD_FEED })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected an update for another feed");
+        assert.include(err.toString(), "InvalidPriceFeed");
+      }
+    });
+
+    it("Should resolve a market from a Switchboard feed", async () => {
+      const feedMarketId = new BN(Date.now() + 51000);
+      const [feedMarketPda] = getMarketPda(feedMarketId);
+      const [feedVaultPda] = getVaultPda(feedMarketId);
+
+      // YES if the reported value is at least 100
+      const condition = {
+        source: { switchboard: {} },
+        feed: SWITCHBOARD_FEED,
+        feedId: new Array(32).fill(0),
+        threshold: new BN(100),
+        thresholdExpo: 0,
+        comparator: { atOrAbove: {} },
+        maxStaleness: FIXTURE_PUBLISH_WINDOW,
+        maxConfidenceBps: 100,
+      };
+
+      await program.methods
+        .createMarket(
+          feedMarketId,
+          "Will the home team score 100+?",
+          "Resolved from a Switchboard feed",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Sports",
+          null,
+          { priceFeed: { 0: condition } },
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: feedMarketPda,
+          vault: feedVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      await program.methods
+        .resolveWithOracle(feedMarketId)
+        .accounts({ market: feedMarketPda, priceFeed: SWITCHBOARD_FEED })
+        .rpc();
+
+      const market = await program.account.market.fetch(feedMarketPda);
+      assert.isTrue("resolved" in market.status);
+      assert.deepEqual(market.outcome, NO); // 98.5 < 100
+    });
+  });
+
+  describe("2️⃣0️⃣  Resolver Committee", () => {
+    async function createCommitteeMarket(id: BN, resolvers: PublicKey[], threshold: number) {
+      const [pda] = getMarketPda(id);
+      const [vault] = getVaultPda(id);
+      await program.methods
+        .createMarket(
+          id,
+          "Committee market",
+          "Resolved by attestations",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Test",
+          null,
+          { committee: { 0: { resolvers, threshold } } },
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: pda,
+          vault: vault,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+      return pda;
+    }
+
+    it("Should resolve once the threshold of attestations agree", async () => {
+      const committeeMarketId = new BN(Date.now() + 60000);
+      const committeeMarketPda = await createCommitteeMarket(
+        committeeMarketId,
+        [creator.publicKey, user1.publicKey, user2.publicKey],
+        2
+      );
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      await program.methods
+        .submitAttestation(committeeMarketId, YES)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, creator.publicKey)[0],
+          resolver: creator.publicKey,
+        })
+        .rpc();
+
+      let market = await program.account.market.fetch(committeeMarketPda);
+      assert.isTrue("active" in market.status);
+      assert.equal(market.attestationCount, 1);
+
+      // Manual resolution is not available to committee markets
+      try {
+        await program.methods
+          .resolveMarket(committeeMarketId, NO, NO_EVIDENCE, null)
+          .accounts({
+            market: committeeMarketPda,
+            resolver: creator.publicKey,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected manual resolution");
+        assert.include(err.toString(), "WrongResolutionMode");
+      }
+
+      await program.methods
+        .submitAttestation(committeeMarketId, YES)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, user1.publicKey)[0],
+          resolver: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      market = await program.account.market.fetch(committeeMarketPda);
+      assert.isTrue("resolved" in market.status);
+      assert.isTrue(market.isFinalized);
+      assert.deepEqual(market.outcome, YES);
+      assert.equal(market.attestationCount, 2);
+    });
+
+    it("Should open a dispute when committee members disagree", async () => {
+      const committeeMarketId = new BN(Date.now() + 61000);
+      const committeeMarketPda = await createCommitteeMarket(
+        committeeMarketId,
+        [creator.publicKey, user1.publicKey],
+        2
+      );
+      const [disputePda] = getDisputePda(committeeMarketId);
+      const [disputeVaultPda] = getDisputeVaultPda(committeeMarketId);
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      try {
+        await program.methods
+          .submitAttestation(committeeMarketId, YES)
+          .accounts({
+            market: committeeMarketPda,
+            attestation: getAttestationPda(committeeMarketId, user2.publicKey)[0],
+            resolver: user2.publicKey,
+          })
+          .signers([user2])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected an attestation from outside the committee");
+        assert.include(err.toString(), "NotCommitteeMember");
+      }
+
+      await program.methods
+        .submitAttestation(committeeMarketId, YES)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, creator.publicKey)[0],
+          resolver: creator.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .submitAttestation(committeeMarketId, NO)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, user1.publicKey)[0],
+          usdcMint: usdcMint,
+          dispute: disputePda,
+          disputeVault: disputeVaultPda,
+          resolver: user1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1])
+        .rpc();
+
+      const market = await program.account.market.fetch(committeeMarketPda);
+      assert.isTrue("disputed" in market.status);
+      assert.isFalse(market.isFinalized);
+
+      const dispute = await program.account.dispute.fetch(disputePda);
+      assert.deepEqual(dispute.proposedOutcome, YES);
+      assert.deepEqual(dispute.counterOutcome, NO);
+    });
+
+    it("Should keep the dispute open to a conflict after an agreeing member passes its accounts", async () => {
+      const committeeMarketId = new BN(Date.now() + 62000);
+      const committeeMarketPda = await createCommitteeMarket(
+        committeeMarketId,
+        [creator.publicKey, user1.publicKey, user2.publicKey],
+        3
+      );
+      const [disputePda] = getDisputePda(committeeMarketId);
+      const [disputeVaultPda] = getDisputeVaultPda(committeeMarketId);
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      await program.methods
+        .submitAttestation(committeeMarketId, YES)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, creator.publicKey)[0],
+          resolver: creator.publicKey,
+        })
+        .rpc();
+
+      // An agreeing attestation cannot create the dispute PDA
+      try {
+        await program.methods
+          .submitAttestation(committeeMarketId, YES)
+          .accounts({
+            market: committeeMarketPda,
+            attestation: getAttestationPda(committeeMarketId, user1.publicKey)[0],
+            usdcMint: usdcMint,
+            dispute: disputePda,
+            disputeVault: disputeVaultPda,
+            resolver: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected dispute accounts on an agreeing attestation");
+        assert.include(err.toString(), "UnexpectedDisputeAccounts");
+      }
+
+      await program.methods
+        .submitAttestation(committeeMarketId, YES)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, user1.publicKey)[0],
+          resolver: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      await program.methods
+        .submitAttestation(committeeMarketId, NO)
+        .accounts({
+          market: committeeMarketPda,
+          attestation: getAttestationPda(committeeMarketId, user2.publicKey)[0],
+          usdcMint: usdcMint,
+          dispute: disputePda,
+          disputeVault: disputeVaultPda,
+          resolver: user2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      const market = await program.account.market.fetch(committeeMarketPda);
+      assert.isTrue("disputed" in market.status);
+      const dispute = await program.account.dispute.fetch(disputePda);
+      assert.deepEqual(dispute.counterOutcome, NO);
+    });
+  });
+
+  describe("2️⃣1️⃣  Resolution Deadline", () => {
+    const deadlineConfig = (resolutionWindow: number) => ({
+      feeRecipient: creator.publicKey,
+      defaultFeeBps: 200,
+      maxFeeBps: 1_000,
+      creatorFeeShareBps: 2_000,
+      minBet: new BN(5_000_000),
+      maxBet: new BN(10_000_000_000),
+      creationBond: new BN(50_000_000),
+      resolutionBond: new BN(25_000_000),
+      challengePeriod: new BN(5),
+      minJurorStake: new BN(1_000_000),
+      commitPeriod: new BN(5),
+      revealPeriod: new BN(5),
+      resolutionWindow: new BN(resolutionWindow),
+      claimWindow: new BN(30 * 24 * 60 * 60),
+    });
+
+    it("Should void an unresolved market once its resolution deadline passes", async () => {
+      const [configPda] = getConfigPda();
+      await program.methods
+        .updateConfig(deadlineConfig(3))
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const expiredMarketId = new BN(Date.now() + 50000);
+      const [expiredMarketPda] = getMarketPda(expiredMarketId);
+      const [expiredVaultPda] = getVaultPda(expiredMarketId);
+      const endTime = Math.floor(Date.now() / 1000) + 2;
+
+      await program.methods
+        .createMarket(
+          expiredMarketId,
+          "Will anyone resolve this market?",
+          "Nobody will",
+          new BN(endTime),
+          "Test",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: expiredMarketPda,
+          vault: expiredVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      let market = await program.account.market.fetch(expiredMarketPda);
+      assert.equal(market.resolutionDeadline.toNumber(), endTime + 3);
+
+      try {
+        await program.methods
+          .voidExpiredMarket(expiredMarketId)
+          .accounts({ market: expiredMarketPda })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected voiding before the deadline");
+        assert.include(err.toString(), "ResolutionDeadlineNotReached");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 7000));
+
+      await program.methods
+        .voidExpiredMarket(expiredMarketId)
+        .accounts({ market: expiredMarketPda })
+        .rpc();
+
+      market = await program.account.market.fetch(expiredMarketPda);
+      assert.deepEqual(market.outcome, INVALID);
+      assert.isTrue("resolved" in market.status);
+      assert.isTrue(market.isFinalized);
+
+      await program.methods
+        .updateConfig(deadlineConfig(7 * 24 * 60 * 60))
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+    });
+
+    it("Should return the creation bond of a market voided after its deadline", async () => {
+      const [configPda] = getConfigPda();
+      const [bondEscrowPda] = getBondEscrowPda();
+      await program.methods
+        .updateConfig(deadlineConfig(3))
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      const expiredMarketId = new BN(Date.now() + 51000);
+      const [expiredMarketPda] = getMarketPda(expiredMarketId);
+      await program.methods
+        .createMarket(
+          expiredMarketId,
+          "Will anyone resolve this community market?",
+          "Created permissionlessly with a bond",
+          new BN(Math.floor(Date.now() / 1000) + 2),
+          "Community",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: expiredMarketPda,
+          vault: getVaultPda(expiredMarketId)[0],
+          bondEscrow: bondEscrowPda,
+          creator: user1.publicKey,
+          creatorTokenAccount: user1TokenAccount,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 7000));
+      await program.methods
+        .voidExpiredMarket(expiredMarketId)
+        .accounts({ market: expiredMarketPda })
+        .rpc();
+
+      // Nobody resolving the market is not the creator's fault: the bond goes back
+      const creatorBefore = await getAccount(provider.connection, user1TokenAccount);
+      await program.methods
+        .settleCreationBond(expiredMarketId)
+        .accounts({
+          market: expiredMarketPda,
+          bondEscrow: bondEscrowPda,
+          destination: user1TokenAccount,
+        })
+        .rpc();
+      const creatorAfter = await getAccount(provider.connection, user1TokenAccount);
+      assert.equal(Number(creatorAfter.amount) - Number(creatorBefore.amount), 50_000_000);
+
+      await program.methods
+        .updateConfig(deadlineConfig(7 * 24 * 60 * 60))
+        .accounts({
+          config: configPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+    });
+  });
+
+  describe("2️⃣2️⃣  Early Resolution", () => {
+    it("Should refund bets placed after the event when resolved early", async () => {
+      const earlyMarketId = new BN(Date.now() + 60000);
+      const [earlyMarketPda] = getMarketPda(earlyMarketId);
+      const [earlyVaultPda] = getVaultPda(earlyMarketId);
+
+      await program.methods
+        .createMarket(
+          earlyMarketId,
+          "Will the team clinch the title?",
+          "Can be decided before the season ends",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: earlyMarketPda,
+          vault: earlyVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const [user1StatsPda] = getUserStatsPda(user1.publicKey);
+      const [user2StatsPda] = getUserStatsPda(user2.publicKey);
+      const [earlyBetPda] = getBetPda(earlyMarketPda, user1.publicKey, new BN(0));
+      const [creatorStatsPda] = getUserStatsPda(creator.publicKey);
+      const [losingBetPda] = getBetPda(earlyMarketPda, creator.publicKey, new BN(1));
+      const [lateBetPda] = getBetPda(earlyMarketPda, user2.publicKey, new BN(2));
+
+      await program.methods
+        .placeBet(earlyMarketId, new BN(10_000_000), BET_YES)
+        .accounts({
+          market: earlyMarketPda,
+          bet: earlyBetPda,
+          userStats: user1StatsPda,
+          vault: earlyVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .placeBet(earlyMarketId, new BN(20_000_000), BET_NO)
+        .accounts({
+          market: earlyMarketPda,
+          bet: losingBetPda,
+          userStats: creatorStatsPda,
+          vault: earlyVaultPda,
+          userTokenAccount: creatorTokenAccount,
+          user: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      // The title is clinched right after the opening bets
+      const lastOpeningBet = await program.account.bet.fetch(losingBetPda);
+      const eventOccurredAt = lastOpeningBet.timestamp.addn(1);
+      await new Promise(resolve => setTimeout(resolve, 3000));
+
+      await program.methods
+        .placeBet(earlyMarketId, new BN(10_000_000), BET_YES)
+        .accounts({
+          market: earlyMarketPda,
+          bet: lateBetPda,
+          userStats: user2StatsPda,
+          vault: earlyVaultPda,
+          userTokenAccount: user2TokenAccount,
+          user: user2.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user2])
+        .rpc();
+
+      try {
+        await program.methods
+          .resolveMarket(earlyMarketId, YES, NO_EVIDENCE, new BN(Math.floor(Date.now() / 1000) + 60))
+          .accounts({
+            market: earlyMarketPda,
+            resolver: creator.publicKey,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected an event time in the future");
+        assert.include(err.toString(), "InvalidEventTime");
+      }
+
+      // Resolved well before end_time, pools rolled back to the last opening bet
+      await program.methods
+        .resolveMarket(earlyMarketId, YES, NO_EVIDENCE, eventOccurredAt)
+        .accounts({
+          market: earlyMarketPda,
+          resolver: creator.publicKey,
+          checkpointBet: losingBetPda,
+          nextBet: lateBetPda,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(earlyMarketPda);
+      assert.isTrue("resolutionProposed" in market.status);
+      assert.equal(market.eventOccurredAt.toNumber(), eventOccurredAt.toNumber());
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(earlyMarketId)
+        .accounts({ market: earlyMarketPda })
+        .rpc();
+
+      const claims = [
+        { user: user1, bet: earlyBetPda, stats: user1StatsPda, tokenAccount: user1TokenAccount },
+        { user: user2, bet: lateBetPda, stats: user2StatsPda, tokenAccount: user2TokenAccount },
+      ];
+      for (const claim of claims) {
+        await program.methods
+          .claimWinnings(earlyMarketId)
+          .accounts({
+            market: earlyMarketPda,
+            bet: claim.bet,
+            userStats: claim.stats,
+            vault: earlyVaultPda,
+            userTokenAccount: claim.tokenAccount,
+            user: claim.user.publicKey,
+          })
+          .signers([claim.user])
+          .rpc();
+      }
+
+      // Same side, but only the bet placed before the event pays out
+      const earlyBetAfter = await program.account.bet.fetch(earlyBetPda);
+      const lateBetAfter = await program.account.bet.fetch(lateBetPda);
+      assert.isAbove(earlyBetAfter.payout.toNumber(), 10_000_000);
+      assert.equal(lateBetAfter.payout.toNumber(), 10_000_000);
+    });
+
+    it("Should pay on-time winners from pre-event pools when a late bet loses", async () => {
+      const earlyMarketId = new BN(Date.now() + 61000);
+      const [earlyMarketPda] = getMarketPda(earlyMarketId);
+      const [earlyVaultPda] = getVaultPda(earlyMarketId);
+
+      await program.methods
+        .createMarket(
+          earlyMarketId,
+          "Which team clinches the title?",
+          "Can be decided before the season ends",
+          new BN(Math.floor(Date.now() / 1000) + 600),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          { categorical: { 0: { labels: ["Home", "Away"] } } },
+          null
+        )
+        .accounts({
+          market: earlyMarketPda,
+          vault: earlyVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const [user1StatsPda] = getUserStatsPda(user1.publicKey);
+      const [user2StatsPda] = getUserStatsPda(user2.publicKey);
+      const [onTimeBetPda] = getBetPda(earlyMarketPda, user1.publicKey, new BN(0));
+      const [lateBetPda] = getBetPda(earlyMarketPda, user2.publicKey, new BN(1));
+
+      await program.methods
+        .placeBet(earlyMarketId, new BN(10_000_000), 0)
+        .accounts({
+          market: earlyMarketPda,
+          bet: onTimeBetPda,
+          userStats: user1StatsPda,
+          vault: earlyVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+
+      const onTimeBet = await program.account.bet.fetch(onTimeBetPda);
+      const eventOccurredAt = onTimeBet.timestamp.addn(1);
+      await new Promise(resolve => setTimeout(resolve, 3000));
+
+      // Bet on the side that already lost
+      await program.methods
+        .placeBet(earlyMarketId, new BN(10_000_000), 1)
+        .accounts({
+          market: earlyMarketPda,
+          bet: lateBetPda,
+          userStats: user2StatsPda,
+          vault: earlyVaultPda,
+          userTokenAccount: user2TokenAccount,
+          user: user2.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user2])
+        .rpc();
+
+      try {
+        await program.methods
+          .resolveMarket(earlyMarketId, { category: { 0: 0 } }, NO_EVIDENCE, eventOccurredAt)
+          .accounts({
+            market: earlyMarketPda,
+            resolver: creator.publicKey,
+            checkpointBet: lateBetPda,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a checkpoint placed after the event");
+        assert.include(err.toString(), "InvalidBetCheckpoint");
+      }
+
+      await program.methods
+        .resolveMarket(earlyMarketId, { category: { 0: 0 } }, NO_EVIDENCE, eventOccurredAt)
+        .accounts({
+          market: earlyMarketPda,
+          resolver: creator.publicKey,
+          checkpointBet: onTimeBetPda,
+          nextBet: lateBetPda,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(earlyMarketPda);
+      assert.deepEqual(market.outcomePools.map((pool) => pool.toNumber()), [10_000_000, 0]);
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(earlyMarketId)
+        .accounts({ market: earlyMarketPda })
+        .rpc();
+
+      const claims = [
+        { user: user1, bet: onTimeBetPda, stats: user1StatsPda, tokenAccount: user1TokenAccount, amount: 9_800_000 },
+        { user: user2, bet: lateBetPda, stats: user2StatsPda, tokenAccount: user2TokenAccount, amount: 10_000_000 },
+      ];
+      for (const claim of claims) {
+        const before = await getAccount(provider.connection, claim.tokenAccount);
+        await program.methods
+          .claimWinnings(earlyMarketId)
+          .accounts({
+            market: earlyMarketPda,
+            bet: claim.bet,
+            userStats: claim.stats,
+            vault: earlyVaultPda,
+            userTokenAccount: claim.tokenAccount,
+            user: claim.user.publicKey,
+          })
+          .signers([claim.user])
+          .rpc();
+        const after = await getAccount(provider.connection, claim.tokenAccount);
+        // Own stake back less the 2% fee, nothing from the refunded late bet
+        assert.equal(Number(after.amount) - Number(before.amount), claim.amount);
+      }
+
+      // Only the fee is left behind
+      const vault = await getAccount(provider.connection, earlyVaultPda);
+      assert.equal(Number(vault.amount), 200_000);
+    });
+  });
+
+  describe("2️⃣3️⃣  Reporting Markets", () => {
+    it("Should resolve by stake-weighted reports and slash the minority", async () => {
+      const reportMarketId = new BN(Date.now() + 70000);
+      const [reportMarketPda] = getMarketPda(reportMarketId);
+      const [reportVaultPda] = getVaultPda(reportMarketId);
+      const [reportingPda] = getReportingPda(reportMarketId);
+      const [reportingVaultPda] = getReportingVaultPda(reportMarketId);
+
+      await program.methods
+        .createMarket(
+          reportMarketId,
+          "Did the launch happen on time?",
+          "Resolved by staked reports after the market ends",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Tech",
+          null,
+          { reporting: { 0: { reportingPeriod: new BN(8), slashBps: 5_000 } } },
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: reportMarketPda,
+          vault: reportVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const reports = [
+        { reporter: user1, tokenAccount: user1TokenAccount, outcome: YES, stake: 10_000_000 },
+        { reporter: user2, tokenAccount: user2TokenAccount, outcome: YES, stake: 5_000_000 },
+        { reporter: creator.payer, tokenAccount: creatorTokenAccount, outcome: NO, stake: 6_000_000 },
+      ];
+      const submitReport = (report: typeof reports[0]) =>
+        program.methods
+          .submitReport(reportMarketId, report.outcome, new BN(report.stake))
+          .accounts({
+            market: reportMarketPda,
+            usdcMint: usdcMint,
+            reporting: reportingPda,
+            reportingVault: reportingVaultPda,
+            report: getReportPda(reportMarketId, report.reporter.publicKey)[0],
+            reporterTokenAccount: report.tokenAccount,
+            reporter: report.reporter.publicKey,
+          })
+          .signers([report.reporter])
+          .rpc();
+
+      try {
+        await submitReport(reports[0]);
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a report before the market ended");
+        assert.include(err.toString(), "MarketNotEnded");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+      for (const report of reports) {
+        await submitReport(report);
+      }
+
+      try {
+        await program.methods
+          .settleReports(reportMarketId)
+          .accounts({ market: reportMarketPda, reporting: reportingPda })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected settling during the reporting period");
+        assert.include(err.toString(), "ReportingPeriodActive");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 8000));
+      await program.methods
+        .settleReports(reportMarketId)
+        .accounts({ market: reportMarketPda, reporting: reportingPda })
+        .rpc();
+
+      const market = await program.account.market.fetch(reportMarketPda);
+      assert.deepEqual(market.outcome, YES);
+      assert.isTrue(market.isFinalized);
+      assert.equal(market.resolver.toString(), reportingPda.toString());
+
+      const reporting = await program.account.reporting.fetch(reportingPda);
+      assert.equal(reporting.rewardPool.toNumber(), 3_000_000); // Half of the NO stake
+
+      // 10 + 2 and 5 + 1 for the majority, half the stake back for the minority
+      const expected = [12_000_000, 6_000_000, 3_000_000];
+      for (const [i, report] of reports.entries()) {
+        const before = await getAccount(provider.connection, report.tokenAccount);
+        await program.methods
+          .claimReport(reportMarketId)
+          .accounts({
+            reporting: reportingPda,
+            reportingVault: reportingVaultPda,
+            report: getReportPda(reportMarketId, report.reporter.publicKey)[0],
+            reporterTokenAccount: report.tokenAccount,
+            reporter: report.reporter.publicKey,
+          })
+          .signers([report.reporter])
+          .rpc();
+        const after = await getAccount(provider.connection, report.tokenAccount);
+        assert.equal(Number(after.amount) - Number(before.amount), expected[i]);
+      }
+    });
+
+    it("Should refund every reporter when an admin voids the market", async () => {
+      const reportMarketId = new BN(Date.now() + 71000);
+      const [reportMarketPda] = getMarketPda(reportMarketId);
+      const [reportVaultPda] = getVaultPda(reportMarketId);
+      const [reportingPda] = getReportingPda(reportMarketId);
+      const [reportingVaultPda] = getReportingVaultPda(reportMarketId);
+
+      await program.methods
+        .createMarket(
+          reportMarketId,
+          "Did the update ship?",
+          "Voided while reports are staked",
+          new BN(Math.floor(Date.now() / 1000) + 3),
+          "Tech",
+          null,
+          { reporting: { 0: { reportingPeriod: new BN(60), slashBps: 5_000 } } },
+          RESOLUTION_SOURCE,
+          BINARY,
+          null
+        )
+        .accounts({
+          market: reportMarketPda,
+          vault: reportVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      const reports = [
+        { reporter: user1, tokenAccount: user1TokenAccount, outcome: YES, stake: 10_000_000 },
+        { reporter: user2, tokenAccount: user2TokenAccount, outcome: NO, stake: 6_000_000 },
+      ];
+      for (const report of reports) {
+        await program.methods
+          .submitReport(reportMarketId, report.outcome, new BN(report.stake))
+          .accounts({
+            market: reportMarketPda,
+            usdcMint: usdcMint,
+            reporting: reportingPda,
+            reportingVault: reportingVaultPda,
+            report: getReportPda(reportMarketId, report.reporter.publicKey)[0],
+            reporterTokenAccount: report.tokenAccount,
+            reporter: report.reporter.publicKey,
+          })
+          .signers([report.reporter])
+          .rpc();
+      }
+
+      await program.methods
+        .voidMarket(reportMarketId)
+        .accounts({
+          market: reportMarketPda,
+          admin: creator.publicKey,
+        })
+        .rpc();
+
+      // No need to wait out the reporting period once the market is void
+      await program.methods
+        .settleReports(reportMarketId)
+        .accounts({ market: reportMarketPda, reporting: reportingPda })
+        .rpc();
+
+      try {
+        await program.methods
+          .settleReports(reportMarketId)
+          .accounts({ market: reportMarketPda, reporting: reportingPda })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected settling the refunded round twice");
+        assert.include(err.toString(), "ReportsAlreadySettled");
+      }
+
+      for (const report of reports) {
+        const before = await getAccount(provider.connection, report.tokenAccount);
+        await program.methods
+          .claimReport(reportMarketId)
+          .accounts({
+            reporting: reportingPda,
+            reportingVault: reportingVaultPda,
+            report: getReportPda(reportMarketId, report.reporter.publicKey)[0],
+            reporterTokenAccount: report.tokenAccount,
+            reporter: report.reporter.publicKey,
+          })
+          .signers([report.reporter])
+          .rpc();
+        const after = await getAccount(provider.connection, report.tokenAccount);
+        assert.equal(Number(after.amount) - Number(before.amount), report.stake);
+      }
+      console.log("✅ Every reporter got their stake back");
+    });
+  });
+
+  describe("2️⃣4️⃣  Categorical Markets", () => {
+    it("Should pay the winning outcome out of every pool", async () => {
+      const catMarketId = new BN(Date.now() + 80000);
+      const [catMarketPda] = getMarketPda(catMarketId);
+      const [catVaultPda] = getVaultPda(catMarketId);
+      const labels = ["Red team", "Blue team", "Green team"];
+
+      try {
+        await program.methods
+          .createMarket(
+            catMarketId,
+            "Who wins the tournament?",
+            "Only one outcome",
+            new BN(Math.floor(Date.now() / 1000) + 4),
+            "Sports",
+            null,
+            MANUAL,
+            RESOLUTION_SOURCE,
+            { categorical: { 0: { labels: ["Red team"] } } },
+            null
+          )
+          .accounts({
+            market: catMarketPda,
+            vault: catVaultPda,
+            creator: creator.publicKey,
+            usdcMint: usdcMint,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a categorical market with one outcome");
+        assert.include(err.toString(), "InvalidOutcomes");
+      }
+
+      await program.methods
+        .createMarket(
+          catMarketId,
+          "Who wins the tournament?",
+          "Three teams left",
+          new BN(Math.floor(Date.now() / 1000) + 4),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          { categorical: { 0: { labels } } },
+          null
+        )
+        .accounts({
+          market: catMarketPda,
+          vault: catVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const bettors = [
+        { user: user1.publicKey, signers: [user1], tokenAccount: user1TokenAccount, index: 0, amount: 10_000_000 },
+        { user: user2.publicKey, signers: [user2], tokenAccount: user2TokenAccount, index: 1, amount: 20_000_000 },
+        { user: creator.publicKey, signers: [], tokenAccount: creatorTokenAccount, index: 2, amount: 30_000_000 },
+      ];
+      const betPdas = bettors.map((bettor, i) => getBetPda(catMarketPda, bettor.user, new BN(i))[0]);
+
+      try {
+        await program.methods
+          .placeBet(catMarketId, new BN(10_000_000), labels.length)
+          .accounts({
+            market: catMarketPda,
+            bet: betPdas[0],
+            userStats: getUserStatsPda(user1.publicKey)[0],
+            vault: catVaultPda,
+            userTokenAccount: user1TokenAccount,
+            user: user1.publicKey,
+            usdcMint: usdcMint,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a bet on a missing outcome");
+        assert.include(err.toString(), "InvalidOutcome");
+      }
+
+      for (const [i, bettor] of bettors.entries()) {
+        await program.methods
+          .placeBet(catMarketId, new BN(bettor.amount), bettor.index)
+          .accounts({
+            market: catMarketPda,
+            bet: betPdas[i],
+            userStats: getUserStatsPda(bettor.user)[0],
+            vault: catVaultPda,
+            userTokenAccount: bettor.tokenAccount,
+            user: bettor.user,
+            usdcMint: usdcMint,
+          })
+          .signers(bettor.signers)
+          .rpc();
+      }
+
+      const market = await program.account.market.fetch(catMarketPda);
+      assert.deepEqual(market.outcomePools.map(pool => pool.toNumber()), [10_000_000, 20_000_000, 30_000_000]);
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      try {
+        await program.methods
+          .resolveMarket(catMarketId, YES, NO_EVIDENCE, null)
+          .accounts({
+            market: catMarketPda,
+            resolver: creator.publicKey,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected YES on a categorical market");
+        assert.include(err.toString(), "InvalidOutcome");
+      }
+
+      const BLUE = { category: { 0: 1 } };
+      await program.methods
+        .resolveMarket(catMarketId, BLUE, NO_EVIDENCE, null)
+        .accounts({
+          market: catMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(catMarketId)
+        .accounts({ market: catMarketPda })
+        .rpc();
+
+      // Blue holds the whole 60 USDC pool, less the 2% fee
+      const expected = [0, 58_800_000, 0];
+      for (const [i, bettor] of bettors.entries()) {
+        const before = await getAccount(provider.connection, bettor.tokenAccount);
+        await program.methods
+          .claimWinnings(catMarketId)
+          .accounts({
+            market: catMarketPda,
+            bet: betPdas[i],
+            userStats: getUserStatsPda(bettor.user)[0],
+            vault: catVaultPda,
+            userTokenAccount: bettor.tokenAccount,
+            user: bettor.user,
+          })
+          .signers(bettor.signers)
+          .rpc();
+        const after = await getAccount(provider.connection, bettor.tokenAccount);
+        assert.equal(Number(after.amount) - Number(before.amount), expected[i]);
+      }
+    });
+  });
+
+  describe("2️⃣5️⃣  Scalar Markets", () => {
+    it("Should split the pool between LONG and SHORT by the settled value", async () => {
+      const scalarMarketId = new BN(Date.now() + 90000);
+      const [scalarMarketPda] = getMarketPda(scalarMarketId);
+      const [scalarVaultPda] = getVaultPda(scalarMarketId);
+      const LONG = 0;
+      const SHORT = 1;
+
+      await program.methods
+        .createMarket(
+          scalarMarketId,
+          "How many points will the team score?",
+          "Pays out between 0 and 100 points",
+          new BN(Math.floor(Date.now() / 1000) + 4),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          { scalar: { 0: { lower: new BN(0), upper: new BN(100) } } },
+          null
+        )
+        .accounts({
+          market: scalarMarketPda,
+          vault: scalarVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const bettors = [
+        { user: user1, tokenAccount: user1TokenAccount, side: LONG, amount: 30_000_000 },
+        { user: user2, tokenAccount: user2TokenAccount, side: SHORT, amount: 10_000_000 },
+      ];
+      const betPdas = bettors.map((bettor, i) => getBetPda(scalarMarketPda, bettor.user.publicKey, new BN(i))[0]);
+
+      for (const [i, bettor] of bettors.entries()) {
+        await program.methods
+          .placeBet(scalarMarketId, new BN(bettor.amount), bettor.side)
+          .accounts({
+            market: scalarMarketPda,
+            bet: betPdas[i],
+            userStats: getUserStatsPda(bettor.user.publicKey)[0],
+            vault: scalarVaultPda,
+            userTokenAccount: bettor.tokenAccount,
+            user: bettor.user.publicKey,
+            usdcMint: usdcMint,
+          })
+          .signers([bettor.user])
+          .rpc();
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      try {
+        await program.methods
+          .resolveMarket(scalarMarketId, YES, NO_EVIDENCE, null)
+          .accounts({
+            market: scalarMarketPda,
+            resolver: creator.publicKey,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected YES on a scalar market");
+        assert.include(err.toString(), "InvalidOutcome");
+      }
+
+      await program.methods
+        .resolveMarket(scalarMarketId, { scalar: { 0: new BN(25) } }, NO_EVIDENCE, null)
+        .accounts({
+          market: scalarMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(scalarMarketId)
+        .accounts({ market: scalarMarketPda })
+        .rpc();
+
+      // 25 of 100: LONG shares 10 USDC of the 40 USDC pool, SHORT 30, both less the 2% fee
+      const expected = [9_800_000, 29_400_000];
+      // LONG gets back less than its stake (a loss), SHORT more (a win)
+      const expectedWins = [0, 1];
+      const expectedLosses = [1, 0];
+      for (const [i, bettor] of bettors.entries()) {
+        const before = await getAccount(provider.connection, bettor.tokenAccount);
+        const statsBefore = await program.account.userStats.fetch(getUserStatsPda(bettor.user.publicKey)[0]);
+        await program.methods
+          .claimWinnings(scalarMarketId)
+          .accounts({
+            market: scalarMarketPda,
+            bet: betPdas[i],
+            userStats: getUserStatsPda(bettor.user.publicKey)[0],
+            vault: scalarVaultPda,
+            userTokenAccount: bettor.tokenAccount,
+            user: bettor.user.publicKey,
+          })
+          .signers([bettor.user])
+          .rpc();
+        const after = await getAccount(provider.connection, bettor.tokenAccount);
+        assert.equal(Number(after.amount) - Number(before.amount), expected[i]);
+
+        const statsAfter = await program.account.userStats.fetch(getUserStatsPda(bettor.user.publicKey)[0]);
+        assert.equal(statsAfter.winCount.toNumber() - statsBefore.winCount.toNumber(), expectedWins[i]);
+        assert.equal(statsAfter.lossCount.toNumber() - statsBefore.lossCount.toNumber(), expectedLosses[i]);
+      }
+    });
+
+    it("Should refund a one-sided scalar market without a fee", async () => {
+      const scalarMarketId = new BN(Date.now() + 91000);
+      const [scalarMarketPda] = getMarketPda(scalarMarketId);
+      const [scalarVaultPda] = getVaultPda(scalarMarketId);
+      const LONG = 0;
+
+      await program.methods
+        .createMarket(
+          scalarMarketId,
+          "How many goals will be scored?",
+          "Pays out between 0 and 10 goals",
+          new BN(Math.floor(Date.now() / 1000) + 4),
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          { scalar: { 0: { lower: new BN(0), upper: new BN(10) } } },
+          null
+        )
+        .accounts({
+          market: scalarMarketPda,
+          vault: scalarVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      // Only LONG is backed
+      const [betPda] = getBetPda(scalarMarketPda, user1.publicKey, new BN(0));
+      const [userStatsPda] = getUserStatsPda(user1.publicKey);
+      await program.methods
+        .placeBet(scalarMarketId, new BN(10_000_000), LONG)
+        .accounts({
+          market: scalarMarketPda,
+          bet: betPda,
+          userStats: userStatsPda,
+          vault: scalarVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      await program.methods
+        .resolveMarket(scalarMarketId, { scalar: { 0: new BN(3) } }, NO_EVIDENCE, null)
+        .accounts({
+          market: scalarMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(scalarMarketId)
+        .accounts({ market: scalarMarketPda })
+        .rpc();
+
+      const before = await getAccount(provider.connection, user1TokenAccount);
+      const statsBefore = await program.account.userStats.fetch(userStatsPda);
+      await program.methods
+        .claimWinnings(scalarMarketId)
+        .accounts({
+          market: scalarMarketPda,
+          bet: betPda,
+          userStats: userStatsPda,
+          vault: scalarVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+      const after = await getAccount(provider.connection, user1TokenAccount);
+
+      // Full stake back, no fee accrued, neither a win nor a loss
+      assert.equal(Number(after.amount) - Number(before.amount), 10_000_000);
+      const market = await program.account.market.fetch(scalarMarketPda);
+      assert.equal(market.protocolFeesAccrued.toNumber(), 0);
+      assert.equal(market.creatorFeesAccrued.toNumber(), 0);
+      const statsAfter = await program.account.userStats.fetch(userStatsPda);
+      assert.equal(statsAfter.winCount.toNumber(), statsBefore.winCount.toNumber());
+      assert.equal(statsAfter.lossCount.toNumber(), statsBefore.lossCount.toNumber());
+      console.log("✅ One-sided scalar market refunded without a fee");
+    });
+  });
+
+  describe("2️⃣6️⃣  Bucket Markets", () => {
+    it("Should pick the winning bucket from the reported value", async () => {
+      const bucketMarketId = new BN(Date.now() + 100000);
+      const [bucketMarketPda] = getMarketPda(bucketMarketId);
+      const [bucketVaultPda] = getVaultPda(bucketMarketId);
+
+      // SOL below 100, 100 to 150, 150 and up
+      await program.methods
+        .createMarket(
+          bucketMarketId,
+          "Where will SOL close?",
+          "Three price buckets",
+          new BN(Math.floor(Date.now() / 1000) + 4),
+          "Crypto",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          { buckets: { 0: { boundaries: [new BN(100), new BN(150)] } } },
+          null
+        )
+        .accounts({
+          market: bucketMarketPda,
+          vault: bucketVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(bucketMarketPda);
+      assert.equal(market.outcomePools.length, 3);
+
+      const bettors = [
+        { user: user1.publicKey, signers: [user1], tokenAccount: user1TokenAccount, bucket: 0, amount: 10_000_000 },
+        { user: user2.publicKey, signers: [user2], tokenAccount: user2TokenAccount, bucket: 1, amount: 20_000_000 },
+        { user: creator.publicKey, signers: [], tokenAccount: creatorTokenAccount, bucket: 2, amount: 10_000_000 },
+      ];
+      const betPdas = bettors.map((bettor, i) => getBetPda(bucketMarketPda, bettor.user, new BN(i))[0]);
+
+      for (const [i, bettor] of bettors.entries()) {
+        await program.methods
+          .placeBet(bucketMarketId, new BN(bettor.amount), bettor.bucket)
+          .accounts({
+            market: bucketMarketPda,
+            bet: betPdas[i],
+            userStats: getUserStatsPda(bettor.user)[0],
+            vault: bucketVaultPda,
+            userTokenAccount: bettor.tokenAccount,
+            user: bettor.user,
+            usdcMint: usdcMint,
+          })
+          .signers(bettor.signers)
+          .rpc();
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      // A bucket's lower boundary belongs to it
+      await program.methods
+        .resolveMarket(bucketMarketId, { scalar: { 0: new BN(150) } }, NO_EVIDENCE, null)
+        .accounts({
+          market: bucketMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(bucketMarketId)
+        .accounts({ market: bucketMarketPda })
+        .rpc();
+
+      // The top bucket takes the 40 USDC pool, less the 2% fee
+      const expected = [0, 0, 39_200_000];
+      for (const [i, bettor] of bettors.entries()) {
+        const before = await getAccount(provider.connection, bettor.tokenAccount);
+        await program.methods
+          .claimWinnings(bucketMarketId)
+          .accounts({
+            market: bucketMarketPda,
+            bet: betPdas[i],
+            userStats: getUserStatsPda(bettor.user)[0],
+            vault: bucketVaultPda,
+            userTokenAccount: bettor.tokenAccount,
+            user: bettor.user,
+          })
+          .signers(bettor.signers)
+          .rpc();
+        const after = await getAccount(provider.connection, bettor.tokenAccount);
+        assert.equal(Number(after.amount) - Number(before.amount), expected[i]);
+      }
+    });
+  });
+
+  describe("2️⃣7️⃣  Conditional Markets", () => {
+    it("Should void a child market when its parent misses the required outcome", async () => {
+      const parentMarketId = new BN(Date.now() + 110000);
+      const childMarketId = new BN(Date.now() + 110001);
+      const [parentMarketPda] = getMarketPda(parentMarketId);
+      const [parentVaultPda] = getVaultPda(parentMarketId);
+      const [childMarketPda] = getMarketPda(childMarketId);
+      const [childVaultPda] = getVaultPda(childMarketId);
+      const endTime = new BN(Math.floor(Date.now() / 1000) + 4);
+
+      await program.methods
+        .createMarket(parentMarketId, "Will candidate A win?", "Parent market", endTime, "Politics", null, MANUAL, RESOLUTION_SOURCE, BINARY, null)
+        .accounts({
+          market: parentMarketPda,
+          vault: parentVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      await program.methods
+        .createMarket(
+          childMarketId,
+          "If candidate A wins, will policy B pass?",
+          "Voided unless candidate A wins",
+          endTime,
+          "Politics",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          BINARY,
+          YES
+        )
+        .accounts({
+          market: childMarketPda,
+          vault: childVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+          parentMarket: parentMarketPda,
+        })
+        .rpc();
+
+      const child = await program.account.market.fetch(childMarketPda);
+      assert.equal(child.parentMarket.toString(), parentMarketPda.toString());
+      assert.deepEqual(child.requiredOutcome, YES);
+
+      const [user1StatsPda] = getUserStatsPda(user1.publicKey);
+      const [childBetPda] = getBetPda(childMarketPda, user1.publicKey, new BN(0));
+      await program.methods
+        .placeBet(childMarketId, new BN(10_000_000), BET_YES)
+        .accounts({
+          market: childMarketPda,
+          bet: childBetPda,
+          userStats: user1StatsPda,
+          vault: childVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+          usdcMint: usdcMint,
+        })
+        .signers([user1])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      try {
+        await program.methods
+          .resolveMarket(childMarketId, YES, NO_EVIDENCE, null)
+          .accounts({
+            market: childMarketPda,
+            resolver: creator.publicKey,
+            parentMarket: parentMarketPda,
+          })
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected resolving the child before its parent");
+        assert.include(err.toString(), "ParentNotResolved");
+      }
+
+      await program.methods
+        .resolveMarket(parentMarketId, NO, NO_EVIDENCE, null)
+        .accounts({
+          market: parentMarketPda,
+          resolver: creator.publicKey,
+        })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      await program.methods
+        .finalizeResolution(parentMarketId)
+        .accounts({ market: parentMarketPda })
+        .rpc();
+
+      // Candidate A lost, so the child is voided whatever the resolver says
+      await program.methods
+        .resolveMarket(childMarketId, YES, NO_EVIDENCE, null)
+        .accounts({
+          market: childMarketPda,
+          resolver: creator.publicKey,
+          parentMarket: parentMarketPda,
+        })
+        .rpc();
+
+      const voided = await program.account.market.fetch(childMarketPda);
+      assert.deepEqual(voided.outcome, INVALID);
+      assert.isTrue(voided.isFinalized);
+
+      const before = await getAccount(provider.connection, user1TokenAccount);
+      await program.methods
+        .claimWinnings(childMarketId)
+        .accounts({
+          market: childMarketPda,
+          bet: childBetPda,
+          userStats: user1StatsPda,
+          vault: childVaultPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+      const after = await getAccount(provider.connection, user1TokenAccount);
+      assert.equal(Number(after.amount) - Number(before.amount), 10_000_000);
+    });
+  });
+
+  describe("2️⃣8️⃣  Parlays", () => {
+    const MAX_LEG_ODDS_BPS = new BN(50_000); // 5x
+
+    it("Should pay a parlay at its locked odds once every leg wins", async () => {
+      const [parlayVaultPda] = getParlayVaultPda();
+
+      await program.methods
+        .configureParlayHouse(new BN(50_000_000), new BN(100_000_000), MAX_LEG_ODDS_BPS, new BN(0))
+        .accounts({
+          admin: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+      await program.methods
+        .fundParlayHouse(new BN(100_000_000))
+        .accounts({
+          funderTokenAccount: creatorTokenAccount,
+          funder: creator.publicKey,
+        })
+        .rpc();
+
+      const endTime = new BN(Math.floor(Date.now() / 1000) + 4);
+      const legIds = [new BN(Date.now() + 120000), new BN(Date.now() + 120001)];
+      const legPdas = legIds.map(id => getMarketPda(id)[0]);
+      for (let i = 0; i < legIds.length; i++) {
+        await program.methods
+          .createMarket(legIds[i], `Parlay leg ${i + 1}?`, "Parlay leg", endTime, "Sports", null, MANUAL, RESOLUTION_SOURCE, BINARY, null)
+          .accounts({
+            market: legPdas[i],
+            vault: getVaultPda(legIds[i])[0],
+            creator: creator.publicKey,
+            usdcMint: usdcMint,
+          })
+          .rpc();
+      }
+      const legAccounts = legPdas.map(pubkey => ({ pubkey, isSigner: false, isWritable: false }));
+
+      // Fresh pools are even, so each leg is locked at 2x
+      try {
+        await program.methods
+          .placeParlay(new BN(0), new BN(20_000_000), Buffer.from([BET_YES, BET_YES]))
+          .accounts({
+            parlay: getParlayPda(user1.publicKey, new BN(0))[0],
+            userTokenAccount: user1TokenAccount,
+            user: user1.publicKey,
+          })
+          .remainingAccounts(legAccounts)
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a parlay above the house max payout");
+        assert.include(err.toString(), "ParlayPayoutTooLarge");
+      }
+
+      const parlayId = new BN(1);
+      const [parlayPda] = getParlayPda(user1.publicKey, parlayId);
+      await program.methods
+        .placeParlay(parlayId, new BN(10_000_000), Buffer.from([BET_YES, BET_NO]))
+        .accounts({
+          parlay: parlayPda,
+          userTokenAccount: user1TokenAccount,
+          user: user1.publicKey,
+        })
+        .remainingAccounts(legAccounts)
+        .signers([user1])
+        .rpc();
+
+      const parlay = await program.account.parlay.fetch(parlayPda);
+      assert.equal(parlay.legs.length, 2);
+      assert.equal(parlay.legs[0].oddsBps.toNumber(), 20_000);
+      assert.equal(parlay.potentialPayout.toNumber(), 40_000_000);
+      assert.deepEqual(parlay.status, { open: {} });
+
+      try {
+        await program.methods
+          .settleParlay(parlayId)
+          .accounts({
+            parlay: parlayPda,
+            parlayVault: parlayVaultPda,
+            userTokenAccount: user1TokenAccount,
+          })
+          .remainingAccounts(legAccounts)
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected settling while legs are open");
+        assert.include(err.toString(), "ParlayLegsOpen");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+      const outcomes = [YES, NO];
+      for (let i = 0; i < legIds.length; i++) {
+        await program.methods
+          .resolveMarket(legIds[i], outcomes[i], NO_EVIDENCE, null)
+          .accounts({
+            market: legPdas[i],
+            resolver: creator.publicKey,
+          })
+          .rpc();
+      }
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      for (let i = 0; i < legIds.length; i++) {
+        await program.methods
+          .finalizeResolution(legIds[i])
+          .accounts({ market: legPdas[i] })
+          .rpc();
+      }
+
+      const before = await getAccount(provider.connection, user1TokenAccount);
+      await program.methods
+        .settleParlay(parlayId)
+        .accounts({
+          parlay: parlayPda,
+          parlayVault: parlayVaultPda,
+          userTokenAccount: user1TokenAccount,
+        })
+        .remainingAccounts(legAccounts)
+        .rpc();
+      const after = await getAccount(provider.connection, user1TokenAccount);
+      assert.equal(Number(after.amount) - Number(before.amount), 40_000_000);
+
+      const settled = await program.account.parlay.fetch(parlayPda);
+      assert.deepEqual(settled.status, { won: {} });
+      const [parlayHousePda] = getParlayHousePda();
+      const house = await program.account.parlayHouse.fetch(parlayHousePda);
+      assert.equal(house.outstandingLiability.toNumber(), 0);
+    });
+
+    it("Should refuse legs whose pools were skewed to inflate the odds", async () => {
+      const endTime = new BN(Math.floor(Date.now() / 1000) + 60);
+      const catMarketId = new BN(Date.now() + 130000);
+      const [catMarketPda] = getMarketPda(catMarketId);
+      const [catVaultPda] = getVaultPda(catMarketId);
+      const binaryMarketId = new BN(Date.now() + 130001);
+      const [binaryMarketPda] = getMarketPda(binaryMarketId);
+
+      await program.methods
+        .createMarket(
+          catMarketId,
+          "Which team wins the cup?",
+          "Parlay manipulation target",
+          endTime,
+          "Sports",
+          null,
+          MANUAL,
+          RESOLUTION_SOURCE,
+          { categorical: { 0: { labels: ["Team A", "Team B", "Team C"] } } },
+          null
+        )
+        .accounts({
+          market: catMarketPda,
+          vault: catVaultPda,
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+      await program.methods
+        .createMarket(binaryMarketId, "Parlay partner leg?", "Parlay leg", endTime, "Sports", null, MANUAL, RESOLUTION_SOURCE, BINARY, null)
+        .accounts({
+          market: binaryMarketPda,
+          vault: getVaultPda(binaryMarketId)[0],
+          creator: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+
+      // A dust stake on Team A and a large one on Team B would price Team A at 11x
+      const TEAM_A = 0;
+      const TEAM_B = 1;
+      const stakes = [
+        { user: user2, tokenAccount: user2TokenAccount, outcome: TEAM_A, amount: 5_000_000 },
+        { user: user1, tokenAccount: user1TokenAccount, outcome: TEAM_B, amount: 50_000_000 },
+      ];
+      for (const [i, stake] of stakes.entries()) {
+        await program.methods
+          .placeBet(catMarketId, new BN(stake.amount), stake.outcome)
+          .accounts({
+            market: catMarketPda,
+            bet: getBetPda(catMarketPda, stake.user.publicKey, new BN(i))[0],
+            userStats: getUserStatsPda(stake.user.publicKey)[0],
+            vault: catVaultPda,
+            userTokenAccount: stake.tokenAccount,
+            user: stake.user.publicKey,
+            usdcMint: usdcMint,
+          })
+          .signers([stake.user])
+          .rpc();
+      }
+
+      const legAccounts = [catMarketPda, binaryMarketPda].map(pubkey => ({ pubkey, isSigner: false, isWritable: false }));
+      try {
+        await program.methods
+          .placeParlay(new BN(2), new BN(5_000_000), Buffer.from([TEAM_A, BET_YES]))
+          .accounts({
+            parlay: getParlayPda(user2.publicKey, new BN(2))[0],
+            userTokenAccount: user2TokenAccount,
+            user: user2.publicKey,
+          })
+          .remainingAccounts(legAccounts)
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a leg priced above the max leg odds");
+        assert.include(err.toString(), "ParlayLegOddsTooHigh");
+      }
+
+      // With a minimum pool the thin market cannot be parlayed at all
+      await program.methods
+        .configureParlayHouse(new BN(50_000_000), new BN(100_000_000), MAX_LEG_ODDS_BPS, new BN(100_000_000))
+        .accounts({
+          admin: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+      try {
+        await program.methods
+          .placeParlay(new BN(3), new BN(5_000_000), Buffer.from([TEAM_B, BET_YES]))
+          .accounts({
+            parlay: getParlayPda(user2.publicKey, new BN(3))[0],
+            userTokenAccount: user2TokenAccount,
+            user: user2.publicKey,
+          })
+          .remainingAccounts(legAccounts)
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (err) {
+        console.log("✅ Correctly rejected a leg market below the min pool");
+        assert.include(err.toString(), "ParlayLegPoolTooSmall");
+      }
+
+      await program.methods
+        .configureParlayHouse(new BN(50_000_000), new BN(100_000_000), MAX_LEG_ODDS_BPS, new BN(0))
+        .accounts({
+          admin: creator.publicKey,
+          usdcMint: usdcMint,
+        })
+        .rpc();
+    });
+  });
+
   after(async () => {
     console.log("\n\n🎉 All tests completed successfully!\n");
     console.log("📊 Final Summary:");